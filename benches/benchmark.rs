@@ -1,4 +1,4 @@
-use criterion::{criterion_group, criterion_main, Criterion, BenchmarkId, black_box};
+use criterion::{criterion_group, criterion_main, Criterion};
 use rustor::model::actor::ActorPool;
 
 fn create_large_graph(n: usize) -> ActorPool {
@@ -24,6 +24,7 @@ fn create_large_graph(n: usize) -> ActorPool {
     pool
 }
 
+#[allow(dead_code)]
 fn create_sparse_subscribe_with_no_loop_graph() {
     let total_actors = 1000;
 
@@ -1 +1,2 @@
+#[allow(dead_code)]
 mod benchmark;
\ No newline at end of file
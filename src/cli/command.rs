@@ -0,0 +1,457 @@
+use std::{
+    fs,
+    io::{self, ErrorKind, Write},
+    path::{Path, PathBuf},
+};
+
+use clap::{Parser, Subcommand, ValueEnum};
+use serde::{Deserialize, Serialize};
+
+use crate::model::{
+    actor::ActorPool,
+    behavior::Counter,
+    errors::ActorError,
+    mailbox::{MailboxConfig, OverflowPolicy},
+    message::Message,
+    propagation::Propagation,
+};
+
+use super::{
+    daemon::{Address, Daemon},
+    repl::Repl,
+    scenario::Scenario,
+};
+
+/// `Cli` is the command line of the `rustor` binary.
+///
+/// Every invocation works on a single pool of counters, loaded from the `--state` file if it exists.
+/// The pool is written back to the file after a command that changed it.
+/// With `--connect`, commands are sent to the pool of a daemon started by `rustor serve` instead.
+#[derive(Debug, Parser)]
+#[command(name = "rustor", about, version)]
+pub struct Cli {
+    /// The JSON file the pool is loaded from and saved to. Without it, every invocation starts from an empty pool.
+    #[arg(short, long, global = true)]
+    pub state: Option<PathBuf>,
+
+    /// The Unix socket or localhost TCP address of a daemon to send the command to.
+    #[arg(long, global = true, conflicts_with = "state")]
+    pub connect: Option<Address>,
+
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+/// `Command` is a subcommand of the `rustor` binary, and a request of the daemon protocol,
+/// where it is written in JSON like `{"command": "send", "actor_id": 0, "message": ["inc", "5"]}`.
+#[derive(Debug, Subcommand, Serialize, Deserialize)]
+#[serde(tag = "command", rename_all = "kebab-case")]
+pub enum Command {
+    /// Create actors and print their ids.
+    #[command(visible_alias = "spawn")]
+    Create {
+        #[arg(default_value_t = 1)]
+        #[serde(default = "one")]
+        count: usize,
+        /// The number of messages the mailboxes hold. Mailbox settings are not saved in the state file.
+        #[arg(short, long, conflicts_with = "unbounded")]
+        capacity: Option<usize>,
+        #[arg(short, long)]
+        #[serde(default)]
+        unbounded: bool,
+        /// What a full mailbox does with a new message.
+        #[arg(short, long, value_enum, default_value_t = Overflow::Reject)]
+        #[serde(default)]
+        overflow: Overflow,
+    },
+    /// Subscribe actors to a target actor.
+    #[command(visible_alias = "sub")]
+    Subscribe {
+        target: usize,
+        #[arg(required = true)]
+        subscribers: Vec<usize>,
+    },
+    /// Unsubscribe actors from a target actor.
+    #[command(visible_alias = "unsub")]
+    Unsubscribe {
+        target: usize,
+        #[arg(required = true)]
+        subscribers: Vec<usize>,
+    },
+    /// Send a message, like `increment 5` or `CompareAndSet(1, 2)`, and wait until it has been handled.
+    ///
+    /// A message sent several times is sent without waiting in between, so that it can overflow the mailbox,
+    /// and the number of rejected copies is printed.
+    Send {
+        #[arg(short = 'n', long, default_value_t = 1)]
+        #[serde(default = "one")]
+        times: usize,
+        actor_id: usize,
+        #[arg(required = true, trailing_var_arg = true, allow_hyphen_values = true)]
+        message: Vec<String>,
+    },
+    /// Print the lifecycle state of an actor, after changing it if asked to.
+    State {
+        actor_id: usize,
+        #[arg(value_enum)]
+        change: Option<StateChange>,
+    },
+    /// Print the value of an actor.
+    Value { actor_id: usize },
+    /// Print the subscribers of an actor.
+    Subscribers { actor_id: usize },
+    /// Print the number of messages waiting in the mailbox and the stash of an actor.
+    Mailbox { actor_id: usize },
+    /// Print the messages that were never delivered or handled successfully.
+    DeadLetters,
+    /// Print how messages are propagated to subscribers, after changing it if asked to.
+    /// The propagation is not saved in the state file.
+    Propagation {
+        #[arg(value_enum)]
+        mode: Option<PropagationMode>,
+    },
+    /// Print one cycle per group of actors that subscribe to each other.
+    Cycles,
+    /// Print whether a cycle is reachable from an actor.
+    DetectCycle {
+        actor_id: usize,
+        #[arg(short, long, value_enum, default_value_t = Algorithm::Dfs)]
+        #[serde(default)]
+        algorithm: Algorithm,
+    },
+    /// Print the subscription graph.
+    Graph {
+        #[arg(short, long, value_enum, default_value_t = GraphFormat::Dot)]
+        #[serde(default)]
+        format: GraphFormat,
+    },
+    /// Run the commands of a script, one per line. Empty lines and lines starting with `#` are ignored.
+    Run { script: PathBuf },
+    /// Run scenario files against new pools and report their expectations. Fails if any expectation fails.
+    Scenario {
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+    },
+    /// Read commands from the standard input until `exit`, keeping the pool between them.
+    Repl,
+    /// Run a daemon that serves the pool on a Unix socket, or on a localhost TCP address like `127.0.0.1:7070`,
+    /// until it receives `shutdown`.
+    Serve { address: Address },
+    /// Stop the daemon given by `--connect`. The pool is saved if the daemon was started with `--state`.
+    Shutdown,
+}
+
+fn one() -> usize {
+    1
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum StateChange {
+    /// Pause an active actor, or resume a paused one.
+    Toggle,
+    Pause,
+    Resume,
+    Restart,
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Default, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Overflow {
+    #[default]
+    Reject,
+    DropOldest,
+    DropNewest,
+    Block,
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PropagationMode {
+    PerPath,
+    ExactlyOnce,
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Default, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Algorithm {
+    #[default]
+    Dfs,
+    Bfs,
+    Topo,
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Default, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum GraphFormat {
+    #[default]
+    Dot,
+    Json,
+}
+
+impl Overflow {
+    pub(crate) fn policy(self) -> OverflowPolicy {
+        match self {
+            Overflow::Reject => OverflowPolicy::Reject,
+            Overflow::DropOldest => OverflowPolicy::DropOldest,
+            Overflow::DropNewest => OverflowPolicy::DropNewest,
+            Overflow::Block => OverflowPolicy::Block(None),
+        }
+    }
+}
+
+impl From<PropagationMode> for Propagation {
+    fn from(mode: PropagationMode) -> Self {
+        match mode {
+            PropagationMode::PerPath => Propagation::PerPath,
+            PropagationMode::ExactlyOnce => Propagation::ExactlyOnce,
+        }
+    }
+}
+
+/// A single line of a script: a command without the binary name.
+#[derive(Debug, Parser)]
+#[command(no_binary_name = true)]
+struct ScriptLine {
+    #[command(subcommand)]
+    command: Command,
+}
+
+impl Command {
+    /// Returns `true` if the command may change the pool, so that the state file must be saved afterwards.
+    pub fn is_mutating(&self) -> bool {
+        matches!(
+            self,
+            Command::Create { .. }
+                | Command::Subscribe { .. }
+                | Command::Unsubscribe { .. }
+                | Command::Send { .. }
+                | Command::State {
+                    change: Some(_),
+                    ..
+                }
+                | Command::Run { .. }
+                | Command::Repl
+                | Command::Serve { .. }
+        )
+    }
+
+    /// Returns `false` for the commands that take over the process or run other commands,
+    /// which can't be used in scripts or sent to a daemon.
+    pub fn is_scriptable(&self) -> bool {
+        !matches!(
+            self,
+            Command::Run { .. } | Command::Repl | Command::Serve { .. } | Command::Shutdown
+        )
+    }
+
+    /// Run the command against `pool` and write its output to `out`.
+    pub fn execute(&self, pool: &ActorPool, out: &mut impl Write) -> Result<(), ActorError> {
+        match self {
+            Command::Create {
+                count,
+                capacity,
+                unbounded,
+                overflow,
+            } => {
+                let mailbox = match (capacity, unbounded) {
+                    (_, true) => MailboxConfig::unbounded(),
+                    (Some(capacity), _) => MailboxConfig::bounded(*capacity, overflow.policy()),
+                    (None, _) => MailboxConfig {
+                        overflow: overflow.policy(),
+                        ..MailboxConfig::default()
+                    },
+                };
+
+                for _ in 0..*count {
+                    let actor = pool.spawn_with_mailbox(Counter, mailbox)?;
+                    writeln!(out, "{}", actor.id())?;
+                }
+            }
+            Command::Subscribe {
+                target,
+                subscribers,
+            } => {
+                pool.subscribe(*target, subscribers.clone())?;
+            }
+            Command::Unsubscribe {
+                target,
+                subscribers,
+            } => {
+                pool.unsubscribe(*target, subscribers.clone())?;
+            }
+            Command::Send {
+                times,
+                actor_id,
+                message,
+            } => {
+                let message: Message = message.join(" ").parse()?;
+
+                if *times == 1 {
+                    pool.message_loop(*actor_id, message)?;
+                } else {
+                    let rejected = (0..*times)
+                        .filter(|_| pool.message_loop(*actor_id, message.clone()).is_err())
+                        .count();
+                    writeln!(out, "{rejected} of {times} rejected")?;
+                }
+                pool.wait_idle();
+            }
+            Command::State { actor_id, change } => {
+                match change {
+                    Some(StateChange::Toggle) => {
+                        pool.update_actor_state(*actor_id)?;
+                    }
+                    Some(StateChange::Pause) => pool.pause_actor(*actor_id)?,
+                    Some(StateChange::Resume) => pool.resume_actor(*actor_id)?,
+                    Some(StateChange::Restart) => pool.restart_actor(*actor_id)?,
+                    None => {}
+                }
+                pool.wait_idle();
+
+                writeln!(out, "{}", pool.get_actor_state(*actor_id)?)?;
+            }
+            Command::Value { actor_id } => {
+                writeln!(out, "{}", pool.get_actor_value(*actor_id)?)?;
+            }
+            Command::Subscribers { actor_id } => {
+                let mut subscribers = pool.get_actor_subscribers(*actor_id)?;
+                subscribers.sort();
+                for subscriber in subscribers {
+                    writeln!(out, "{subscriber}")?;
+                }
+            }
+            Command::Mailbox { actor_id } => {
+                writeln!(out, "mailbox: {}", pool.get_mailbox_len(*actor_id)?)?;
+                writeln!(out, "stash: {}", pool.get_stash_len(*actor_id)?)?;
+            }
+            Command::DeadLetters => {
+                for letter in pool.dead_letters() {
+                    writeln!(
+                        out,
+                        "{} {}: {}",
+                        letter.target, letter.message, letter.reason
+                    )?;
+                }
+            }
+            Command::Propagation { mode } => {
+                if let Some(mode) = mode {
+                    pool.set_propagation((*mode).into());
+                }
+                writeln!(out, "{:?}", pool.propagation())?;
+            }
+            Command::Cycles => {
+                for cycle in pool.find_cycles() {
+                    let cycle: Vec<String> = cycle.iter().map(|id| id.to_string()).collect();
+                    writeln!(out, "{}", cycle.join(" -> "))?;
+                }
+            }
+            Command::DetectCycle {
+                actor_id,
+                algorithm,
+            } => {
+                let found = match algorithm {
+                    Algorithm::Dfs => pool.detect_cycle_dfs(*actor_id)?,
+                    Algorithm::Bfs => pool.detect_cycle_bfs(*actor_id)?,
+                    Algorithm::Topo => pool.detect_cycle_topological_sort(*actor_id)?,
+                };
+                writeln!(out, "{found}")?;
+            }
+            Command::Graph { format } => match format {
+                GraphFormat::Dot => write!(out, "{}", pool.to_dot())?,
+                GraphFormat::Json => writeln!(out, "{}", pool.to_json()?)?,
+            },
+            Command::Run { script } => {
+                let script = fs::read_to_string(script)?;
+                run_script(&script, pool, out)?;
+            }
+            Command::Scenario { files } => {
+                let (mut failed, mut total) = (0, 0);
+
+                for file in files {
+                    writeln!(out, "{}", file.display())?;
+
+                    let report = fs::read_to_string(file)?.parse::<Scenario>()?.run()?;
+                    writeln!(out, "{report}")?;
+
+                    failed += report.failures().len();
+                    total += report.checks.len();
+                }
+
+                if failed > 0 {
+                    return Err(ActorError::AssertionFailed(format!(
+                        "{failed} of {total} expectations failed"
+                    )));
+                }
+            }
+            Command::Repl => {
+                Repl::new(pool).run(io::stdin().lock(), out)?;
+            }
+            Command::Serve { address } => {
+                let daemon = Daemon::bind(address)?;
+                writeln!(out, "listening on {}", daemon.address())?;
+                out.flush()?;
+
+                daemon.serve(pool)?;
+            }
+            Command::Shutdown => {
+                return Err(ActorError::InvalidOperation(
+                    "there is no daemon to shut down without --connect".to_string(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Run every command of `script` against `pool`, stopping at the first error.
+///
+/// Errors are prefixed with the number of the line that caused them. Scripts can't use `run` or `repl`.
+pub fn run_script(script: &str, pool: &ActorPool, out: &mut impl Write) -> Result<(), ActorError> {
+    for (number, line) in script.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let at_line =
+            |error: String| ActorError::InvalidOperation(format!("line {}: {error}", number + 1));
+
+        let command = ScriptLine::try_parse_from(line.split_whitespace())
+            .map_err(|error| at_line(error.render().to_string().trim_end().to_string()))?
+            .command;
+        if !command.is_scriptable() {
+            return Err(at_line(
+                "scripts can't run scripts, the REPL or a daemon".to_string(),
+            ));
+        }
+
+        command
+            .execute(pool, out)
+            .map_err(|error| at_line(error.to_string()))?;
+    }
+
+    Ok(())
+}
+
+/// Load the pool saved in `path`, or create an empty pool if the file doesn't exist.
+pub fn load_pool(path: Option<&Path>) -> Result<ActorPool, ActorError> {
+    let Some(path) = path else {
+        return Ok(ActorPool::new());
+    };
+
+    match fs::read_to_string(path) {
+        Ok(json) => ActorPool::from_json(&json),
+        Err(error) if error.kind() == ErrorKind::NotFound => Ok(ActorPool::new()),
+        Err(error) => Err(error.into()),
+    }
+}
+
+/// Save `pool` to `path` once every message sent so far has been handled.
+pub fn save_pool(pool: &ActorPool, path: &Path) -> Result<(), ActorError> {
+    pool.wait_idle();
+    fs::write(path, pool.to_json()?)?;
+
+    Ok(())
+}
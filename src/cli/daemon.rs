@@ -0,0 +1,304 @@
+use std::{
+    fmt,
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    str::FromStr,
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
+
+#[cfg(unix)]
+use std::{
+    fs,
+    os::unix::{
+        fs::FileTypeExt,
+        net::{UnixListener, UnixStream},
+    },
+    path::PathBuf,
+};
+
+use crate::model::{
+    actor::ActorPool,
+    errors::ActorError,
+    line::{self, write_line},
+};
+
+use super::command::Command;
+
+/// `Address` is where a daemon listens: the path of a Unix socket, or a localhost TCP address like `127.0.0.1:7070`.
+/// Unix sockets are only available on Unix platforms.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Address {
+    #[cfg(unix)]
+    Unix(PathBuf),
+    Tcp(SocketAddr),
+}
+
+/// `Daemon` serves an `ActorPool` to other processes, with a line-delimited JSON protocol.
+///
+/// Every request is a `Command` on a single line, like `{"command": "value", "actor_id": 0}`, and gets
+/// a single line in response: `{"Ok": "<output of the command>"}` or `{"Err": <ActorError>}`.
+/// Clients can send as many requests as they want over a connection, and connections are served concurrently.
+/// The daemon stops once it receives `{"command": "shutdown"}`, after answering the requests it is serving.
+pub struct Daemon {
+    listener: Listener,
+    shutdown: AtomicBool,
+}
+
+enum Listener {
+    #[cfg(unix)]
+    Unix(UnixListener, PathBuf),
+    Tcp(TcpListener),
+}
+
+enum Stream {
+    #[cfg(unix)]
+    Unix(UnixStream),
+    Tcp(TcpStream),
+}
+
+impl Daemon {
+    /// Listen on `address`. Fails if another daemon is listening there.
+    ///
+    /// A socket file left by a daemon that didn't shut down is replaced,
+    /// but any other file at `address` is left alone and the daemon doesn't start.
+    pub fn bind(address: &Address) -> Result<Self, ActorError> {
+        let listener = match address {
+            #[cfg(unix)]
+            Address::Unix(path) => {
+                if path.exists() {
+                    if !fs::metadata(path)?.file_type().is_socket() {
+                        return Err(ActorError::InvalidOperation(format!(
+                            "{} exists and is not a socket",
+                            path.display()
+                        )));
+                    }
+                    if UnixStream::connect(path).is_ok() {
+                        return Err(ActorError::InvalidOperation(format!(
+                            "a daemon is already listening on {}",
+                            path.display()
+                        )));
+                    }
+                    fs::remove_file(path)?;
+                }
+
+                Listener::Unix(UnixListener::bind(path)?, path.clone())
+            }
+            Address::Tcp(address) => Listener::Tcp(TcpListener::bind(address)?),
+        };
+
+        // accepting without blocking, so that the daemon notices when it must shut down
+        match &listener {
+            #[cfg(unix)]
+            Listener::Unix(listener, _) => listener.set_nonblocking(true)?,
+            Listener::Tcp(listener) => listener.set_nonblocking(true)?,
+        }
+
+        Ok(Daemon {
+            listener,
+            shutdown: AtomicBool::new(false),
+        })
+    }
+
+    /// The address the daemon listens on, with the actual port if it was bound to port `0`.
+    pub fn address(&self) -> Address {
+        match &self.listener {
+            #[cfg(unix)]
+            Listener::Unix(_, path) => Address::Unix(path.clone()),
+            Listener::Tcp(listener) => Address::Tcp(
+                listener
+                    .local_addr()
+                    .expect("a bound listener has a local address"),
+            ),
+        }
+    }
+
+    /// Serve `pool` until a client asks the daemon to shut down.
+    pub fn serve(&self, pool: &ActorPool) -> Result<(), ActorError> {
+        line::accept_connections(
+            &self.shutdown,
+            || self.accept(),
+            |stream| {
+                let _ = self.serve_connection(pool, stream);
+            },
+        )?;
+
+        Ok(())
+    }
+
+    fn accept(&self) -> io::Result<Stream> {
+        let stream = match &self.listener {
+            #[cfg(unix)]
+            Listener::Unix(listener, _) => Stream::Unix(listener.accept()?.0),
+            Listener::Tcp(listener) => Stream::Tcp(listener.accept()?.0),
+        };
+
+        stream.set_nonblocking(false)?;
+        stream.set_read_timeout(Some(line::POLL_INTERVAL))?;
+
+        Ok(stream)
+    }
+
+    fn serve_connection(&self, pool: &ActorPool, stream: Stream) -> io::Result<()> {
+        line::serve_lines(stream.try_clone()?, stream, &self.shutdown, |request| {
+            self.handle(pool, request)
+        })
+    }
+
+    fn handle(&self, pool: &ActorPool, request: &str) -> Result<String, ActorError> {
+        let command: Command = serde_json::from_str(request)
+            .map_err(|error| ActorError::InvalidMessage(error.to_string()))?;
+
+        if let Command::Shutdown = command {
+            self.shutdown.store(true, Ordering::SeqCst);
+            return Ok(String::new());
+        }
+        if !command.is_scriptable() {
+            return Err(ActorError::InvalidOperation(
+                "this command can't be sent to a daemon".to_string(),
+            ));
+        }
+
+        let mut output = Vec::new();
+        command.execute(pool, &mut output)?;
+
+        Ok(String::from_utf8_lossy(&output).into_owned())
+    }
+}
+
+impl Drop for Daemon {
+    fn drop(&mut self) {
+        #[cfg(unix)]
+        if let Listener::Unix(_, path) = &self.listener {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+/// Send `command` to the daemon listening on `address` and return its output.
+///
+/// Errors of the command are returned as they were raised by the daemon's pool.
+pub fn request(address: &Address, command: &Command) -> Result<String, ActorError> {
+    let mut stream = Stream::connect(address)?;
+    write_line(&mut stream, command)?;
+
+    let mut response = String::new();
+    BufReader::new(stream).read_line(&mut response)?;
+    if response.is_empty() {
+        return Err(ActorError::Io(format!(
+            "{address} closed the connection without answering"
+        )));
+    }
+
+    serde_json::from_str::<Result<String, ActorError>>(&response)
+        .map_err(|error| ActorError::InvalidMessage(error.to_string()))?
+}
+
+impl Stream {
+    fn connect(address: &Address) -> io::Result<Self> {
+        match address {
+            #[cfg(unix)]
+            Address::Unix(path) => UnixStream::connect(path).map(Stream::Unix),
+            Address::Tcp(address) => TcpStream::connect(address).map(Stream::Tcp),
+        }
+    }
+
+    fn try_clone(&self) -> io::Result<Self> {
+        match self {
+            #[cfg(unix)]
+            Stream::Unix(stream) => stream.try_clone().map(Stream::Unix),
+            Stream::Tcp(stream) => stream.try_clone().map(Stream::Tcp),
+        }
+    }
+
+    fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+        match self {
+            #[cfg(unix)]
+            Stream::Unix(stream) => stream.set_nonblocking(nonblocking),
+            Stream::Tcp(stream) => stream.set_nonblocking(nonblocking),
+        }
+    }
+
+    fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        match self {
+            #[cfg(unix)]
+            Stream::Unix(stream) => stream.set_read_timeout(timeout),
+            Stream::Tcp(stream) => stream.set_read_timeout(timeout),
+        }
+    }
+}
+
+impl Read for Stream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            #[cfg(unix)]
+            Stream::Unix(stream) => stream.read(buf),
+            Stream::Tcp(stream) => stream.read(buf),
+        }
+    }
+}
+
+impl Write for Stream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            #[cfg(unix)]
+            Stream::Unix(stream) => stream.write(buf),
+            Stream::Tcp(stream) => stream.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            #[cfg(unix)]
+            Stream::Unix(stream) => stream.flush(),
+            Stream::Tcp(stream) => stream.flush(),
+        }
+    }
+}
+
+impl FromStr for Address {
+    type Err = ActorError;
+
+    /// A TCP address must be a loopback address, so that the pool is only reachable from the same machine.
+    /// Anything else is the path of a Unix socket, where they are available.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.parse::<SocketAddr>() {
+            Ok(address) if address.ip().is_loopback() => Ok(Address::Tcp(address)),
+            Ok(address) => Err(ActorError::InvalidOperation(format!(
+                "{address} is not a localhost address"
+            ))),
+            Err(_) if s.is_empty() => Err(ActorError::InvalidOperation(
+                "a daemon address can't be empty".to_string(),
+            )),
+            #[cfg(unix)]
+            Err(_) => Ok(Address::Unix(PathBuf::from(s))),
+            #[cfg(not(unix))]
+            Err(_) => Err(ActorError::InvalidOperation(format!(
+                "{s} is not a TCP address, and Unix sockets are not available on this platform"
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            #[cfg(unix)]
+            Address::Unix(path) => write!(f, "{}", path.display()),
+            Address::Tcp(address) => write!(f, "{address}"),
+        }
+    }
+}
+
+impl serde::Serialize for Address {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Address {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let address = String::deserialize(deserializer)?;
+        address.parse().map_err(serde::de::Error::custom)
+    }
+}
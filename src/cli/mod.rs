@@ -0,0 +1,4 @@
+pub mod command;
+pub mod daemon;
+pub mod repl;
+pub mod scenario;
\ No newline at end of file
@@ -0,0 +1,111 @@
+use std::io::{BufRead, Write};
+
+use clap::{Parser, Subcommand};
+
+use crate::model::{actor::ActorPool, errors::ActorError};
+
+use super::command::Command;
+
+/// The prompt printed before every command.
+pub static PROMPT: &str = "rustor> ";
+
+/// `Repl` is an interactive shell over a single `ActorPool`.
+///
+/// It reads one command per line, like the lines of a script, but keeps going after a failed command
+/// and keeps the pool between commands, so that propagation and mailbox overflows can be explored live.
+pub struct Repl<'a> {
+    pool: &'a ActorPool,
+    history: Vec<String>,
+}
+
+/// The commands of the REPL: every command of the `rustor` binary, plus `history` and `exit`.
+#[derive(Debug, Parser)]
+#[command(name = "rustor", no_binary_name = true, disable_version_flag = true)]
+struct ReplLine {
+    #[command(subcommand)]
+    command: ReplCommand,
+}
+
+#[derive(Debug, Subcommand)]
+enum ReplCommand {
+    #[command(flatten)]
+    Pool(Command),
+    /// Print the commands entered so far.
+    History,
+    /// Leave the REPL.
+    #[command(visible_alias = "quit")]
+    Exit,
+}
+
+impl<'a> Repl<'a> {
+    pub fn new(pool: &'a ActorPool) -> Self {
+        Repl {
+            pool,
+            history: Vec::new(),
+        }
+    }
+
+    /// The commands entered so far, oldest first.
+    pub fn history(&self) -> &[String] {
+        &self.history
+    }
+
+    /// Run the commands read from `input` until `exit` or the end of the input.
+    pub fn run(&mut self, input: impl BufRead, out: &mut impl Write) -> Result<(), ActorError> {
+        write!(out, "{PROMPT}")?;
+        out.flush()?;
+
+        for line in input.lines() {
+            if !self.execute(&line?, out)? {
+                return Ok(());
+            }
+
+            write!(out, "{PROMPT}")?;
+            out.flush()?;
+        }
+
+        writeln!(out)?;
+        Ok(())
+    }
+
+    /// Run a single line and return `false` if the REPL must stop.
+    ///
+    /// A command that fails prints its error to `out`, only failing to write to `out` is an error.
+    pub fn execute(&mut self, line: &str, out: &mut impl Write) -> Result<bool, ActorError> {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            return Ok(true);
+        }
+
+        self.history.push(line.to_string());
+
+        let command = match ReplLine::try_parse_from(line.split_whitespace()) {
+            Ok(parsed) => parsed.command,
+            // usage errors, and the output of `help`
+            Err(error) => {
+                write!(out, "{}", error.render())?;
+                return Ok(true);
+            }
+        };
+
+        match command {
+            ReplCommand::Exit => return Ok(false),
+            ReplCommand::History => {
+                for (number, line) in self.history.iter().enumerate() {
+                    writeln!(out, "{:>4}  {line}", number + 1)?;
+                }
+            }
+            ReplCommand::Pool(Command::Repl) => writeln!(out, "error: already in the REPL")?,
+            ReplCommand::Pool(Command::Serve { .. } | Command::Shutdown) => {
+                writeln!(out, "error: the REPL can't run a daemon")?
+            }
+            ReplCommand::Pool(command) => {
+                if let Err(error) = command.execute(self.pool, out) {
+                    writeln!(out, "error: {error}")?;
+                }
+            }
+        }
+
+        Ok(true)
+    }
+}
@@ -0,0 +1,458 @@
+use std::{fmt, str::FromStr, thread, time::Duration};
+
+use clap::ValueEnum;
+
+use crate::model::{
+    actor::ActorPool,
+    behavior::Counter,
+    errors::ActorError,
+    mailbox::MailboxConfig,
+    message::Message,
+    propagation::Propagation,
+    state::ActorState,
+    timer::{TimerHandle, TIMER_TICK},
+};
+
+use super::command::{Overflow, PropagationMode};
+
+/// `Scenario` is a reproducible simulation of a pool of counters, written with one statement per line:
+///
+/// ```text
+/// # a diamond: actor 3 receives the message once per path
+/// actors 4
+/// edge 0 -> 1 2
+/// edge 1 -> 3
+/// edge 2 -> 3
+/// send 0 inc 1
+/// at 50ms send 3 mul 10
+/// expect value 3 = 20
+/// expect dead-letters = 0
+/// ```
+///
+/// Statements run in order against a new pool:
+/// - `actors <count> [capacity <n> | unbounded] [overflow <policy>]` creates actors, numbered from `0`;
+/// - `propagation per-path|exactly-once` and `edge <from> -> <to>...` set up the propagation;
+/// - `send <id> <message>` sends a message, and `at <delay> send <id> <message>` sends it `delay` after
+///   the statement, without waiting for it. Messages that can't be delivered become dead letters;
+/// - `pause <id>` and `resume <id>` change the state of an actor;
+/// - `wait <delay>` sleeps, and `settle` waits until every message sent so far has been handled.
+///
+/// Expectations are checked once every message has been sent and handled, wherever they appear:
+/// `expect value <id> = <n>`, `expect state <id> = <state>`, `expect subscribers <id> = <id>...`,
+/// `expect mailbox <id> = <n>`, `expect stash <id> = <n>`, `expect dead-letters [<id>] = <n>`
+/// and `expect cycles = <n>`. Delays are written like `250ms` or `2s`, and `#` starts a comment.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Scenario {
+    statements: Vec<(usize, Statement)>,
+    expectations: Vec<(usize, Expectation)>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+enum Statement {
+    Actors {
+        count: usize,
+        mailbox: MailboxConfig,
+    },
+    Propagation(Propagation),
+    Edge {
+        from: usize,
+        to: Vec<usize>,
+    },
+    Send {
+        at: Option<Duration>,
+        actor_id: usize,
+        message: Message,
+    },
+    Pause(usize),
+    Resume(usize),
+    Wait(Duration),
+    Settle,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+enum Expectation {
+    Value(usize, i32),
+    State(usize, ActorState),
+    Subscribers(usize, Vec<usize>),
+    Mailbox(usize, usize),
+    Stash(usize, usize),
+    DeadLetters(Option<usize>, usize),
+    Cycles(usize),
+}
+
+/// The outcome of a single expectation of a scenario.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Check {
+    /// The line of the expectation in the scenario.
+    pub line: usize,
+    /// What was checked, like `value of 3`.
+    pub subject: String,
+    pub expected: String,
+    pub actual: String,
+}
+
+/// The outcome of every expectation of a scenario, in the order they were written.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ScenarioReport {
+    pub checks: Vec<Check>,
+}
+
+impl Scenario {
+    /// Run the scenario against a new pool and check its expectations.
+    ///
+    /// Fails if a statement can't be run, e.g. an edge between unknown actors;
+    /// unmet expectations are reported by the `ScenarioReport` instead.
+    pub fn run(&self) -> Result<ScenarioReport, ActorError> {
+        let pool = ActorPool::new();
+        let mut timers = Vec::new();
+
+        for (line, statement) in self.statements.iter() {
+            statement
+                .execute(&pool, &mut timers)
+                .map_err(|error| at_line(*line, error.to_string()))?;
+        }
+
+        while timers.iter().any(TimerHandle::is_pending) {
+            thread::sleep(TIMER_TICK);
+        }
+        pool.wait_idle();
+
+        let checks = self
+            .expectations
+            .iter()
+            .map(|(line, expectation)| expectation.check(*line, &pool))
+            .collect();
+
+        Ok(ScenarioReport { checks })
+    }
+}
+
+impl FromStr for Scenario {
+    type Err = ActorError;
+
+    /// Fails with `ActorError::InvalidOperation` on the first line that is not a valid statement.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut scenario = Scenario {
+            statements: Vec::new(),
+            expectations: Vec::new(),
+        };
+
+        for (number, line) in s.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default();
+            let words: Vec<&str> = line.split_whitespace().collect();
+
+            match words.as_slice() {
+                [] => {}
+                ["expect", rest @ ..] => {
+                    let expectation =
+                        parse_expectation(rest).map_err(|error| at_line(number + 1, error))?;
+                    scenario.expectations.push((number + 1, expectation));
+                }
+                words => {
+                    let statement =
+                        parse_statement(words).map_err(|error| at_line(number + 1, error))?;
+                    scenario.statements.push((number + 1, statement));
+                }
+            }
+        }
+
+        Ok(scenario)
+    }
+}
+
+impl Statement {
+    fn execute(&self, pool: &ActorPool, timers: &mut Vec<TimerHandle>) -> Result<(), ActorError> {
+        match self {
+            Statement::Actors { count, mailbox } => {
+                for _ in 0..*count {
+                    pool.spawn_with_mailbox(Counter, *mailbox)?;
+                }
+            }
+            Statement::Propagation(propagation) => pool.set_propagation(*propagation),
+            Statement::Edge { from, to } => {
+                pool.subscribe(*from, to.clone())?;
+            }
+            Statement::Send {
+                at: None,
+                actor_id,
+                message,
+            } => {
+                // the message is recorded as a dead letter if it is rejected
+                let _ = pool.message_loop(*actor_id, message.clone());
+            }
+            Statement::Send {
+                at: Some(delay),
+                actor_id,
+                message,
+            } => {
+                timers.push(pool.send_after(*actor_id, *delay, message.clone())?);
+            }
+            Statement::Pause(actor_id) => pool.pause_actor(*actor_id)?,
+            Statement::Resume(actor_id) => pool.resume_actor(*actor_id)?,
+            Statement::Wait(delay) => thread::sleep(*delay),
+            Statement::Settle => pool.wait_idle(),
+        }
+
+        Ok(())
+    }
+}
+
+impl Expectation {
+    fn check(&self, line: usize, pool: &ActorPool) -> Check {
+        let actual = match *self {
+            Expectation::Value(actor_id, _) => {
+                pool.get_actor_value(actor_id).map(|v| v.to_string())
+            }
+            Expectation::State(actor_id, _) => {
+                pool.get_actor_state(actor_id).map(|s| s.to_string())
+            }
+            Expectation::Subscribers(actor_id, _) => {
+                pool.get_actor_subscribers(actor_id).map(render_ids)
+            }
+            Expectation::Mailbox(actor_id, _) => {
+                pool.get_mailbox_len(actor_id).map(|n| n.to_string())
+            }
+            Expectation::Stash(actor_id, _) => pool.get_stash_len(actor_id).map(|n| n.to_string()),
+            Expectation::DeadLetters(Some(actor_id), _) => {
+                Ok(pool.dead_letters_for(actor_id).len().to_string())
+            }
+            Expectation::DeadLetters(None, _) => Ok(pool.dead_letters().len().to_string()),
+            Expectation::Cycles(_) => Ok(pool.find_cycles().len().to_string()),
+        };
+
+        Check {
+            line,
+            subject: self.subject(),
+            expected: self.expected(),
+            actual: actual.unwrap_or_else(|error| format!("error: {error}")),
+        }
+    }
+
+    fn subject(&self) -> String {
+        match *self {
+            Expectation::Value(actor_id, _) => format!("value of {actor_id}"),
+            Expectation::State(actor_id, _) => format!("state of {actor_id}"),
+            Expectation::Subscribers(actor_id, _) => format!("subscribers of {actor_id}"),
+            Expectation::Mailbox(actor_id, _) => format!("mailbox of {actor_id}"),
+            Expectation::Stash(actor_id, _) => format!("stash of {actor_id}"),
+            Expectation::DeadLetters(Some(actor_id), _) => format!("dead letters of {actor_id}"),
+            Expectation::DeadLetters(None, _) => "dead letters".to_string(),
+            Expectation::Cycles(_) => "cycles".to_string(),
+        }
+    }
+
+    fn expected(&self) -> String {
+        match self {
+            Expectation::Value(_, value) => value.to_string(),
+            Expectation::State(_, state) => state.to_string(),
+            Expectation::Subscribers(_, ids) => render_ids(ids.clone()),
+            Expectation::Mailbox(_, n)
+            | Expectation::Stash(_, n)
+            | Expectation::DeadLetters(_, n)
+            | Expectation::Cycles(n) => n.to_string(),
+        }
+    }
+}
+
+impl Check {
+    pub fn passed(&self) -> bool {
+        self.expected == self.actual
+    }
+}
+
+impl ScenarioReport {
+    pub fn passed(&self) -> bool {
+        self.checks.iter().all(Check::passed)
+    }
+
+    pub fn failures(&self) -> Vec<&Check> {
+        self.checks.iter().filter(|check| !check.passed()).collect()
+    }
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.passed() {
+            write!(
+                f,
+                "ok   line {}: {} = {}",
+                self.line, self.subject, self.expected
+            )
+        } else {
+            writeln!(f, "FAIL line {}: {}", self.line, self.subject)?;
+            writeln!(f, "       expected: {}", self.expected)?;
+            write!(f, "       actual:   {}", self.actual)
+        }
+    }
+}
+
+impl fmt::Display for ScenarioReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for check in self.checks.iter() {
+            writeln!(f, "{check}")?;
+        }
+
+        let failed = self.failures().len();
+        write!(f, "{} passed, {failed} failed", self.checks.len() - failed)
+    }
+}
+
+fn at_line(line: usize, error: String) -> ActorError {
+    ActorError::InvalidOperation(format!("line {line}: {error}"))
+}
+
+/// Subscriber ids in increasing order, or `none`.
+fn render_ids(mut ids: Vec<usize>) -> String {
+    if ids.is_empty() {
+        return "none".to_string();
+    }
+
+    ids.sort();
+    let ids: Vec<String> = ids.iter().map(|id| id.to_string()).collect();
+    ids.join(" ")
+}
+
+fn parse_statement(words: &[&str]) -> Result<Statement, String> {
+    let statement = match words {
+        ["actors", count, options @ ..] => Statement::Actors {
+            count: parse(count, "an actor count")?,
+            mailbox: parse_mailbox(options)?,
+        },
+        ["propagation", mode] => {
+            let mode = PropagationMode::from_str(mode, true)?;
+            Statement::Propagation(mode.into())
+        }
+        ["edge", from, "->", to @ ..] if !to.is_empty() => Statement::Edge {
+            from: parse(from, "an actor id")?,
+            to: to
+                .iter()
+                .map(|id| parse(id, "an actor id"))
+                .collect::<Result<_, _>>()?,
+        },
+        ["send", actor_id, message @ ..] => Statement::Send {
+            at: None,
+            actor_id: parse(actor_id, "an actor id")?,
+            message: parse_message(message)?,
+        },
+        ["at", delay, "send", actor_id, message @ ..] => Statement::Send {
+            at: Some(parse_delay(delay)?),
+            actor_id: parse(actor_id, "an actor id")?,
+            message: parse_message(message)?,
+        },
+        ["pause", actor_id] => Statement::Pause(parse(actor_id, "an actor id")?),
+        ["resume", actor_id] => Statement::Resume(parse(actor_id, "an actor id")?),
+        ["wait", delay] => Statement::Wait(parse_delay(delay)?),
+        ["settle"] => Statement::Settle,
+        _ => return Err(format!("can't understand {:?}", words.join(" "))),
+    };
+
+    Ok(statement)
+}
+
+fn parse_expectation(words: &[&str]) -> Result<Expectation, String> {
+    let invalid = || format!("can't understand \"expect {}\"", words.join(" "));
+
+    let position = words
+        .iter()
+        .position(|&word| word == "=")
+        .ok_or_else(invalid)?;
+    let (subject, expected) = (&words[..position], &words[position + 1..]);
+
+    let expectation = match (subject, expected) {
+        (["value", actor_id], [value]) => {
+            Expectation::Value(parse(actor_id, "an actor id")?, parse(value, "a value")?)
+        }
+        (["state", actor_id], [state]) => {
+            Expectation::State(parse(actor_id, "an actor id")?, parse_state(state)?)
+        }
+        (["subscribers", actor_id], ["none"]) => {
+            Expectation::Subscribers(parse(actor_id, "an actor id")?, Vec::new())
+        }
+        (["subscribers", actor_id], ids) => Expectation::Subscribers(
+            parse(actor_id, "an actor id")?,
+            ids.iter()
+                .map(|id| parse(id, "an actor id"))
+                .collect::<Result<_, _>>()?,
+        ),
+        (["mailbox", actor_id], [n]) => {
+            Expectation::Mailbox(parse(actor_id, "an actor id")?, parse(n, "a count")?)
+        }
+        (["stash", actor_id], [n]) => {
+            Expectation::Stash(parse(actor_id, "an actor id")?, parse(n, "a count")?)
+        }
+        (["dead-letters"], [n]) => Expectation::DeadLetters(None, parse(n, "a count")?),
+        (["dead-letters", actor_id], [n]) => {
+            Expectation::DeadLetters(Some(parse(actor_id, "an actor id")?), parse(n, "a count")?)
+        }
+        (["cycles"], [n]) => Expectation::Cycles(parse(n, "a count")?),
+        _ => return Err(invalid()),
+    };
+
+    Ok(expectation)
+}
+
+fn parse_mailbox(options: &[&str]) -> Result<MailboxConfig, String> {
+    let mut mailbox = MailboxConfig::default();
+    let mut options = options.iter();
+
+    while let Some(&option) = options.next() {
+        match option {
+            "capacity" => {
+                let capacity = options
+                    .next()
+                    .ok_or("capacity needs a number of messages")?;
+                mailbox.capacity = Some(parse(capacity, "a capacity")?);
+            }
+            "unbounded" => mailbox.capacity = None,
+            "overflow" => {
+                let policy = options.next().ok_or("overflow needs a policy")?;
+                mailbox.overflow = Overflow::from_str(policy, true)?.policy();
+            }
+            option => return Err(format!("unknown actor option {option:?}")),
+        }
+    }
+
+    if mailbox.capacity == Some(0) {
+        return Err("a bounded mailbox needs a capacity of at least one message".to_string());
+    }
+
+    Ok(mailbox)
+}
+
+fn parse_message(words: &[&str]) -> Result<Message, String> {
+    words
+        .join(" ")
+        .parse()
+        .map_err(|error: ActorError| error.to_string())
+}
+
+fn parse_state(word: &str) -> Result<ActorState, String> {
+    use ActorState::*;
+
+    [
+        Starting, Active, Paused, Restarting, Stopping, Stopped, Failed,
+    ]
+    .into_iter()
+    .find(|state| state.to_string().eq_ignore_ascii_case(word))
+    .ok_or_else(|| format!("{word:?} is not an actor state"))
+}
+
+/// A delay like `250ms` or `2s`.
+fn parse_delay(word: &str) -> Result<Duration, String> {
+    let invalid = || format!("{word:?} is not a delay, like 250ms or 2s");
+
+    if let Some(millis) = word.strip_suffix("ms") {
+        millis
+            .parse()
+            .map(Duration::from_millis)
+            .map_err(|_| invalid())
+    } else if let Some(secs) = word.strip_suffix('s') {
+        secs.parse().map(Duration::from_secs).map_err(|_| invalid())
+    } else {
+        Err(invalid())
+    }
+}
+
+fn parse<T: FromStr>(word: &str, what: &str) -> Result<T, String> {
+    word.parse().map_err(|_| format!("{word:?} is not {what}"))
+}
@@ -1,7 +1,29 @@
 pub mod model {
     pub mod actor;
+    pub mod actor_ref;
+    pub mod dead_letter;
+    pub mod behavior;
+    pub mod envelope;
     pub mod state;
     pub mod message;
+    pub mod propagation;
+    pub mod registry;
+    pub mod remote;
     pub mod errors;
+    pub mod export;
+    pub mod graph;
+    pub mod ids;
+    pub mod lifecycle;
+    pub(crate) mod line;
+    pub mod mailbox;
+    pub mod scheduler;
+    pub mod supervisor;
+    pub mod timer;
+}
+pub mod cli {
+    pub mod command;
+    pub mod daemon;
+    pub mod repl;
+    pub mod scenario;
 }
 mod test;
\ No newline at end of file
@@ -1,50 +1,40 @@
-use clap::{Parser, Subcommand};
-
-
-// https://medium.com/@ukpaiugochi0/building-a-cli-from-scratch-with-clapv3-fb9dc5938c82
-
-fn main() {}
-
-// #[derive(Parser)]
-// #[clap(about, version, author)]
-// struct Value {
-//     #[clap(subcommand)]
-//     cmd: Commands,
-// }
-
-// #[derive(Subcommand)]
-// enum Commands {
-//     Add { n: u32 },
-//     Recv { msg: String, actor_id: usize },
-//     // Send { msg: String, actor_id: usize },
-// }
-
-// fn cli() {
-//     let value = Value::parse();
-
-//     match value.cmd {
-//         // `cargo run -- add`
-//         Commands::Add { n } => {
-//             println!("Add {} actors", n);
-//             for _ in 0..n {
-//                 let actor = ActorPool::new().create_actor();
-//                 println!("Actor id: {}", actor);
-//             }
-//         }
-//         Commands::Recv { msg, actor_id } => match msg.to_ascii_uppercase() {
-//             msg if msg == "STATE" => {
-//                 let state = ActorPool::new().get_actor_state(actor_id);
-//                 println!("{actor_id} state: {state:?}");
-//             }
-//             msg if msg == "VALUE" => {
-//                 let value = ActorPool::new().get_actor_value(actor_id);
-//                 println!("{actor_id} value: {value:?}",);
-//             }
-//             _ => println!("Invalid message"),
-//         },
-//     }
-// }
-
-// fn main() {
-//     cli();
-// }
+use std::{io, process::ExitCode};
+
+use clap::Parser;
+
+use rustor::{
+    cli::{
+        command::{self, Cli, Command},
+        daemon,
+    },
+    model::errors::ActorError,
+};
+
+fn main() -> ExitCode {
+    match run(Cli::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("rustor: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(cli: Cli) -> Result<(), ActorError> {
+    // scenarios build their own pools, so they always run here
+    if let Some(address) = cli.connect.as_ref() {
+        if !matches!(cli.command, Command::Scenario { .. }) {
+            print!("{}", daemon::request(address, &cli.command)?);
+            return Ok(());
+        }
+    }
+
+    let pool = command::load_pool(cli.state.as_deref())?;
+
+    cli.command.execute(&pool, &mut io::stdout().lock())?;
+
+    match cli.state {
+        Some(path) if cli.command.is_mutating() => command::save_pool(&pool, &path),
+        _ => Ok(()),
+    }
+}
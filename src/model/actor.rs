@@ -1,96 +1,814 @@
 use std::{
+    any::Any,
     collections::{HashMap, HashSet, VecDeque},
+    panic::{self, AssertUnwindSafe},
     sync::{
-        atomic::{AtomicUsize, Ordering},
-        Arc, Condvar, Mutex, RwLock,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, Condvar, Mutex, MutexGuard, OnceLock, RwLock, Weak,
     },
+    time::{Duration, Instant},
 };
 
-use super::{errors::ActorError, message::Message, state::ActorState};
+use super::{
+    actor_ref::ActorRef,
+    behavior::{Behavior, Counter},
+    dead_letter::{DeadLetter, DeadLetterOffice, DeadLetterReason},
+    envelope::{Envelope, Metadata, Reply},
+    errors::ActorError,
+    graph::{add_edges_acyclic, GraphAnalysis},
+    ids::{IdAllocator, IdReuse},
+    lifecycle::{ShutdownReport, StopMode, StopReport},
+    mailbox::{MailboxConfig, OverflowPolicy},
+    propagation::{self, Propagation, Settings},
+    registry::Registry,
+    remote::{Link, Remotes},
+    scheduler::Scheduler,
+    state::ActorState,
+    supervisor::{Supervisor, SupervisorSpec},
+    timer::{TimerHandle, Timers},
+};
 
-static ACTOR_ID: AtomicUsize = AtomicUsize::new(0);
-static MAILBOX_CAPACITY: usize = 10;
-static INITIAL_VALUE: i32 = 0;
+static POOL_ID: AtomicUsize = AtomicUsize::new(0);
 
 /// `ActorPool` is a container for actors. Also, it is provide a interface to manage actors.
+///
+/// Every actor in a pool is driven by the same kind of `Behavior`, which is `Counter` by default.
+/// Pools for user-defined behaviors are created with `ActorPool::<B>::default()`.
 #[derive(Debug)]
-pub struct ActorPool {
+pub struct ActorPool<B: Behavior = Counter> {
+    /// `pool_id` identifies the pool, so that `ActorRef`s of different pools can't be mixed up.
+    pub pool_id: usize,
     /// `actor_list` is a container for actors.
     /// its key is `Actor`'s id and value is `Actor` itself.
-    pub actor_list: Mutex<HashMap<usize, Arc<Actor>>>,
+    /// Shared with the `RemoteServer`s exposing the pool.
+    pub actor_list: Arc<Mutex<HashMap<usize, Arc<Actor<B>>>>>,
+    /// Hands out the ids of the actors of this pool, starting from `0`.
+    pub(crate) ids: IdAllocator,
+    /// `scheduler` runs the actors of this pool on its worker threads.
+    pub scheduler: Arc<Scheduler>,
+    /// The subscription graph of the pool. Shared with the `ActorRef`s of its actors.
+    topology: Arc<Topology<B>>,
+    /// How messages reach the subscribers of their target. Shared with every actor of the pool.
+    propagation: Arc<RwLock<Settings>>,
+    /// Records the messages that were never delivered or handled. Shared with every actor of the pool.
+    pub(crate) dead_letters: Arc<DeadLetterOffice<B>>,
+    /// The names of the actors. Shared with every actor of the pool, which unregister themselves when they stop.
+    registry: Arc<Registry>,
+    /// Delivers delayed and periodic messages. Its thread is only started with the first timer.
+    timers: OnceLock<Timers>,
+    /// The connections to the other pools this pool holds remote actors of.
+    pub(crate) remotes: Remotes,
 }
 
 impl ActorPool {
     pub fn new() -> Self {
         Default::default()
     }
+}
 
-    /// Create a new actor and add it to the actor list
-    pub fn create_actor(&self) -> usize {
-        let actor = Actor::new();
-        let id = actor.get_id();
-        let actor_clone = Arc::clone(&actor);
+impl<B: Behavior> ActorPool<B> {
+    /// Create a pool whose actors run on `scheduler`, which may be shared with other pools.
+    pub fn with_scheduler(scheduler: Arc<Scheduler>) -> Self {
+        let actor_list = Arc::new(Mutex::new(HashMap::new()));
 
-        // each `Actor` runs on an independent thread when it is created,
-        // and when it receives a message, it consumes and processes the message in its own mailbox (via `Actor::execute_messages`).
-        std::thread::spawn(move || {
-            actor_clone.execute_messages();
-        });
+        ActorPool {
+            pool_id: POOL_ID.fetch_add(1, Ordering::SeqCst),
+            topology: Arc::new(Topology::new(Arc::clone(&actor_list))),
+            actor_list,
+            ids: IdAllocator::default(),
+            scheduler,
+            propagation: Arc::new(RwLock::new(Settings::default())),
+            dead_letters: Arc::new(DeadLetterOffice::new()),
+            registry: Arc::new(Registry::default()),
+            timers: OnceLock::new(),
+            remotes: Remotes::default(),
+        }
+    }
+
+    /// Create a new actor driven by the default behavior and add it to the actor list
+    pub fn create_actor(&self) -> usize
+    where
+        B: Default,
+    {
+        self.create_actor_with(B::default())
+    }
+
+    /// Create a new actor driven by `behavior` and add it to the actor list
+    pub fn create_actor_with(&self, behavior: B) -> usize {
+        self.spawn(behavior).id()
+    }
+
+    /// Create a new actor driven by `behavior` and return a typed handle to it.
+    pub fn spawn(&self, behavior: B) -> ActorRef<B> {
+        self.spawn_actor(self.ids.allocate().0, behavior, MailboxConfig::default())
+    }
+
+    /// Create a new actor driven by `behavior` whose mailbox is configured by `mailbox`.
+    pub fn spawn_with_mailbox(
+        &self,
+        behavior: B,
+        mailbox: MailboxConfig,
+    ) -> Result<ActorRef<B>, ActorError> {
+        if mailbox.capacity == Some(0) {
+            return Err(ActorError::InvalidOperation(
+                "a bounded mailbox needs a capacity of at least one message".to_string(),
+            ));
+        }
+
+        Ok(self.spawn_actor(self.ids.allocate().0, behavior, mailbox))
+    }
+
+    /// Create a new actor driven by `behavior` and register it under `name` (see `ActorPool::register`).
+    pub fn spawn_named(&self, name: &str, behavior: B) -> Result<ActorRef<B>, ActorError> {
+        self.registry.check_available(name)?;
+
+        let actor_ref = self.spawn(behavior);
+        if let Err(error) = self.register(name, actor_ref.id()) {
+            // the name was taken in the meantime
+            self.remove_actor(actor_ref.id(), StopMode::Discard)?;
+            return Err(error);
+        }
+
+        Ok(actor_ref)
+    }
+
+    pub(crate) fn spawn_actor(
+        &self,
+        id: usize,
+        behavior: B,
+        mailbox: MailboxConfig,
+    ) -> ActorRef<B> {
+        self.insert_actor(self.new_actor(id, behavior, mailbox))
+    }
+
+    /// Create an actor that stands for a remote actor: every message it receives is forwarded through `link`.
+    pub(crate) fn spawn_proxy(&self, behavior: B, link: Arc<dyn Link<B>>) -> ActorRef<B> {
+        let actor = self.new_actor(self.ids.allocate().0, behavior, MailboxConfig::unbounded());
+        // the link is set before any message can reach the actor
+        let _ = actor.remote.set(link);
+
+        self.insert_actor(actor)
+    }
+
+    fn new_actor(&self, id: usize, behavior: B, mailbox: MailboxConfig) -> Arc<Actor<B>> {
+        // `Actor`s don't own a thread. Whenever a message arrives in an idle actor's mailbox,
+        // the actor is scheduled on one of the `scheduler`'s workers and consumes its mailbox there (via `Actor::execute_messages`).
+        Actor::new(
+            id,
+            behavior,
+            mailbox,
+            Arc::clone(&self.scheduler),
+            Arc::clone(&self.propagation),
+            Arc::clone(&self.dead_letters),
+            Arc::clone(&self.registry),
+        )
+    }
+
+    fn insert_actor(&self, actor: Arc<Actor<B>>) -> ActorRef<B> {
+        let id = actor.id;
+        let actor_ref = ActorRef::new(self.pool_id, self.generation_of(id), &actor, &self.topology);
+
+        self.actor_list
+            .lock()
+            .unwrap()
+            .insert(id, Arc::clone(&actor));
+        // a new actor can't have been stopped yet
+        let _ = actor.transition(ActorState::Active);
+
+        actor_ref
+    }
+
+    /// Register an actor under a unique `name`, either a plain name like `ledger`
+    /// or a hierarchical path like `/orders/shard-3`.
+    ///
+    /// An actor has at most one name. It keeps it while it is paused or restarted, and loses it
+    /// as soon as it stops or fails, so that a replacement can be registered under the same name.
+    /// Fails with `ActorError::ActorAlreadyExists` if the name is taken or the actor already has another name.
+    pub fn register(&self, name: &str, actor_id: usize) -> Result<(), ActorError> {
+        let actor = self.get_actor_info(actor_id)?;
+
+        self.registry
+            .register(name, actor_id, || !actor.is_stopped())
+    }
+
+    /// Remove a name from the registry and return the id of the actor it belonged to.
+    pub fn unregister(&self, name: &str) -> Result<usize, ActorError> {
+        self.registry
+            .unregister(name)
+            .ok_or(ActorError::TargetActorNotFound(name.to_string()))
+    }
+
+    /// Get a handle to the actor registered under `name`.
+    pub fn lookup(&self, name: &str) -> Result<ActorRef<B>, ActorError> {
+        let actor_id = self
+            .registry
+            .lookup(name)
+            .ok_or(ActorError::TargetActorNotFound(name.to_string()))?;
+
+        self.actor_ref(actor_id)
+    }
+
+    /// The name of an actor, if it is registered.
+    pub fn actor_name(&self, actor_id: usize) -> Option<String> {
+        self.registry.name_of(actor_id)
+    }
+
+    /// Every name below `path` (e.g. `/orders/shard-3` below `/orders`) with the id of its actor,
+    /// ordered by name.
+    pub fn registered_under(&self, path: &str) -> Vec<(String, usize)> {
+        self.registry.under(path)
+    }
+
+    /// Get a typed handle to an existing actor.
+    pub fn actor_ref(&self, actor_id: usize) -> Result<ActorRef<B>, ActorError> {
+        let actor = self.get_actor_info(actor_id)?;
+
+        Ok(ActorRef::new(
+            self.pool_id,
+            self.generation_of(actor_id),
+            &actor,
+            &self.topology,
+        ))
+    }
+
+    /// Like `actor_ref`, but fails with `ActorError::TargetActorNotFound` when `actor_id` was reused
+    /// by a newer actor than the one of the given `generation` (see `ActorPool::set_id_reuse`).
+    pub fn actor_ref_at(
+        &self,
+        actor_id: usize,
+        generation: u64,
+    ) -> Result<ActorRef<B>, ActorError> {
+        let actor_ref = self.actor_ref(actor_id)?;
+
+        if actor_ref.generation() != generation {
+            return Err(ActorError::TargetActorNotFound(format!(
+                "{actor_id} (generation {generation})"
+            )));
+        }
+
+        Ok(actor_ref)
+    }
+
+    /// The generation of an actor's id: `0` unless the id belonged to removed actors before.
+    pub fn actor_generation(&self, actor_id: usize) -> Result<u64, ActorError> {
+        self.get_actor_info(actor_id)?;
+
+        Ok(self.generation_of(actor_id))
+    }
+
+    fn generation_of(&self, actor_id: usize) -> u64 {
+        self.ids.generation(actor_id).unwrap_or_default()
+    }
+
+    /// Choose whether the ids of removed actors are handed out again. `IdReuse::Never` is used by default.
+    pub fn set_id_reuse(&self, policy: IdReuse) {
+        self.ids.set_policy(policy);
+    }
+
+    pub fn id_reuse(&self) -> IdReuse {
+        self.ids.policy()
+    }
+
+    /// Resolve a handle into the actor it points to,
+    /// rejecting handles that were created by another pool.
+    pub fn resolve(&self, actor_ref: &ActorRef<B>) -> Result<Arc<Actor<B>>, ActorError> {
+        if actor_ref.pool_id() != self.pool_id {
+            return Err(ActorError::InvalidOperation(format!(
+                "actor {} belongs to pool {}, not to pool {}",
+                actor_ref.id(),
+                actor_ref.pool_id(),
+                self.pool_id
+            )));
+        }
+
+        actor_ref.upgrade()
+    }
+
+    /// Stop an actor and wait until it is no longer running on a worker.
+    ///
+    /// The actor stays in the pool (its value and subscribers can still be read),
+    /// but any further message is rejected with `ActorError::TargetActorIsOffline`.
+    /// The actor is unhooked from the subscriber list of every other actor, so that their messages
+    /// aren't propagated to it anymore.
+    pub fn stop_actor(
+        &self,
+        actor_id: usize,
+        mode: StopMode,
+    ) -> Result<StopReport<B::Message>, ActorError> {
+        let actor = self.get_actor_info(actor_id)?;
+
+        let mut dropped = actor.stop(mode);
+        dropped.extend(self.join_actor(&actor));
+
+        let _topology = self.topology.lock();
+        self.unhook(actor_id);
+
+        Ok(StopReport { actor_id, dropped })
+    }
+
+    /// Stop an actor, wait for it and remove it from the pool.
+    /// The actor is also unhooked from the subscriber list of every other actor.
+    pub fn remove_actor(
+        &self,
+        actor_id: usize,
+        mode: StopMode,
+    ) -> Result<StopReport<B::Message>, ActorError> {
+        let report = self.stop_actor(actor_id, mode)?;
+        let _topology = self.topology.lock();
+
+        let removed = self.actor_list.lock().unwrap().remove(&actor_id);
+        if let Some(actor) = removed {
+            actor.subs.write().unwrap().clear();
+            self.ids.release(actor_id);
+        }
+
+        self.unhook(actor_id);
+
+        Ok(report)
+    }
+
+    /// Remove `actor_id` from the subscriber list of every actor of the pool.
+    fn unhook(&self, actor_id: usize) {
+        for actor in self.actor_list.lock().unwrap().values() {
+            actor.subs.write().unwrap().remove(&actor_id);
+        }
+    }
+
+    /// Gracefully stop every actor of the pool, wait for them and empty the pool.
+    /// Pending timers are cancelled.
+    pub fn shutdown(&self, mode: StopMode) -> ShutdownReport<B::Message> {
+        if let Some(timers) = self.timers.get() {
+            timers.cancel_all();
+        }
+
+        let actors: Vec<Arc<Actor<B>>> = {
+            let mut actor_list = self.actor_list.lock().unwrap();
+            actor_list.drain().map(|(_, actor)| actor).collect()
+        };
+
+        // stop every actor first, so that draining mailboxes happens in parallel
+        let mut dropped: HashMap<usize, Vec<B::Message>> = actors
+            .iter()
+            .map(|actor| (actor.id, actor.stop(mode)))
+            .collect();
+
+        for actor in actors.iter() {
+            let leftover = self.join_actor(actor);
+            dropped.entry(actor.id).or_default().extend(leftover);
 
-        let mut actor_list = self.actor_list.lock().unwrap();
-        actor_list.insert(id, actor);
+            // subscriptions hold strong references, so clear them to break cycles
+            actor.subs.write().unwrap().clear();
+            self.ids.release(actor.id);
+        }
+
+        dropped.retain(|_, messages| !messages.is_empty());
+
+        let mut stopped: Vec<usize> = actors.iter().map(|actor| actor.id).collect();
+        stopped.sort();
+
+        ShutdownReport { stopped, dropped }
+    }
+
+    /// Block until no actor of the pool is queued or running, e.g. until every message sent so far,
+    /// including the propagated copies, has been handled. Messages stashed by paused actors are not waited for.
+    pub fn wait_idle(&self) {
+        let actors: Vec<Arc<Actor<B>>> =
+            self.actor_list.lock().unwrap().values().cloned().collect();
+
+        for actor in actors {
+            actor.wait_idle();
+        }
+    }
+
+    /// Wait for a stopped actor to leave its worker and return the messages it never handled.
+    fn join_actor(&self, actor: &Arc<Actor<B>>) -> Vec<B::Message> {
+        actor.wait_idle();
+        actor.finish_stopping();
+
+        actor.take_mailbox()
+    }
+
+    /// Make `supervisor_id` the supervisor of `children`.
+    ///
+    /// From then on, a child whose handler returns an error for a message sent without `ask`
+    /// or panics is restarted according to `spec` instead of being stopped.
+    /// Children are ordered as given, which matters for `RestartStrategy::RestForOne`.
+    pub fn supervise(
+        &self,
+        supervisor_id: usize,
+        children: Vec<usize>,
+        spec: SupervisorSpec<B::State>,
+    ) -> Result<Arc<Supervisor<B>>, ActorError> {
+        let parent = self.get_actor_info(supervisor_id)?;
+
+        if parent.supervising.read().unwrap().is_some() {
+            return Err(ActorError::ActorAlreadyExists(format!(
+                "supervisor {supervisor_id}"
+            )));
+        }
 
-        id
+        let mut child_actors = Vec::with_capacity(children.len());
+        for child_id in children {
+            let child = self.get_actor_info(child_id)?;
+
+            if child_id == supervisor_id || child.supervisor.read().unwrap().is_some() {
+                return Err(ActorError::InvalidOperation(format!(
+                    "actor {child_id} can't be supervised by {supervisor_id}"
+                )));
+            }
+
+            child_actors.push(child);
+        }
+
+        let supervisor = Arc::new(Supervisor::new(&parent, &child_actors, spec));
+
+        for child in child_actors {
+            *child.supervisor.write().unwrap() = Some(Arc::clone(&supervisor));
+        }
+        *parent.supervising.write().unwrap() = Some(Arc::clone(&supervisor));
+
+        Ok(supervisor)
     }
 
+    /// Get the supervisor run by `supervisor_id`.
+    pub fn get_supervisor(&self, supervisor_id: usize) -> Result<Arc<Supervisor<B>>, ActorError> {
+        let actor = self.get_actor_info(supervisor_id)?;
+        let supervising = actor.supervising.read().unwrap();
+
+        supervising
+            .clone()
+            .ok_or(ActorError::InvalidOperation(format!(
+                "actor {supervisor_id} doesn't supervise any actor"
+            )))
+    }
+
+    /// The state of an actor. For a remote actor, the state of the actor in its own pool.
     pub fn get_actor_state(&self, actor_id: usize) -> Result<ActorState, ActorError> {
         let actor = self.get_actor_info(actor_id)?;
 
-        actor.get_state()
+        actor.reported_state()
     }
 
+    /// Toggle an actor between `ActorState::Active` and `ActorState::Paused`
+    /// (see `ActorPool::pause_actor` and `ActorPool::resume_actor`).
     pub fn update_actor_state(&self, actor_id: usize) -> Result<ActorState, ActorError> {
         let actor = self.get_actor_info(actor_id)?;
-        let mut state = actor.state.write().unwrap();
 
         // Change the current state to the opposite state
-        match state.to_owned() {
-            ActorState::Active => *state = ActorState::Inactive,
-            ActorState::Inactive => *state = ActorState::Active,
+        match actor.get_state()? {
+            ActorState::Paused => actor.resume()?,
+            _ => actor.pause()?,
         }
 
-        Ok(state.to_owned())
+        actor.get_state()
+    }
+
+    /// Pause an active actor: it stashes the messages it receives instead of handling them,
+    /// together with the ones waiting in its mailbox (see `MailboxConfig::stash_limit`).
+    /// The message being handled, if any, is not interrupted.
+    pub fn pause_actor(&self, actor_id: usize) -> Result<(), ActorError> {
+        self.get_actor_info(actor_id)?.pause()
     }
 
-    pub fn get_actor_value(&self, actor_id: usize) -> Result<i32, ActorError> {
+    /// Resume a paused actor. The stashed messages are replayed in the order they were received,
+    /// before any message sent afterwards. The replay happens on the scheduler, like any other message:
+    /// use `wait_idle` to wait for it.
+    pub fn resume_actor(&self, actor_id: usize) -> Result<(), ActorError> {
+        self.get_actor_info(actor_id)?.resume()
+    }
+
+    /// Reset an actor to `Behavior::initial_state`, going through `ActorState::Restarting`.
+    /// The actor keeps its mailbox, its stash and its subscriptions.
+    pub fn restart_actor(&self, actor_id: usize) -> Result<(), ActorError> {
+        let actor = self.get_actor_info(actor_id)?;
+
+        actor.restart(actor.behavior.initial_state())
+    }
+
+    /// The value of an actor. For a remote actor, it is read from its own pool.
+    pub fn get_actor_value(&self, actor_id: usize) -> Result<B::State, ActorError> {
         let actor = self.get_actor_info(actor_id)?;
 
         actor.get_value()
     }
 
+    /// Number of messages waiting in the actor's mailbox.
+    pub fn get_mailbox_len(&self, actor_id: usize) -> Result<usize, ActorError> {
+        let actor = self.get_actor_info(actor_id)?;
+        let mailbox = actor.mailbox.lock().unwrap();
+
+        Ok(mailbox.len())
+    }
+
+    /// Number of messages stashed while the actor is paused.
+    pub fn get_stash_len(&self, actor_id: usize) -> Result<usize, ActorError> {
+        let actor = self.get_actor_info(actor_id)?;
+        let stash = actor.stash.lock().unwrap();
+
+        Ok(stash.len())
+    }
+
+    /// The messages stashed while the actor is paused, in the order they will be replayed.
+    pub fn get_stashed_messages(&self, actor_id: usize) -> Result<Vec<B::Message>, ActorError> {
+        let actor = self.get_actor_info(actor_id)?;
+        let stash = actor.stash.lock().unwrap();
+
+        Ok(stash
+            .iter()
+            .map(|envelope| envelope.message.clone())
+            .collect())
+    }
+
+    /// Throw away the messages stashed by a paused actor, so that they are never replayed.
+    /// They are recorded as dead letters and returned.
+    pub fn discard_stash(&self, actor_id: usize) -> Result<Vec<B::Message>, ActorError> {
+        let actor = self.get_actor_info(actor_id)?;
+
+        Ok(actor.discard_stash())
+    }
+
     pub fn get_actor_subscribers(&self, actor_id: usize) -> Result<Vec<usize>, ActorError> {
         let actor = self.get_actor_info(actor_id)?;
 
         Ok(actor.get_subscribers())
     }
 
+    /// Send `message` to an actor once `delay` has elapsed, as with `ActorPool::message_loop`.
+    ///
+    /// Every timer of the pool runs on a single timer thread with a resolution of `TIMER_TICK`.
+    /// A message whose actor is gone or stopped by then becomes a dead letter.
+    /// Remote actors can't be sent timed messages, see `ActorPool::remote_actor`.
+    pub fn send_after(
+        &self,
+        actor_id: usize,
+        delay: Duration,
+        message: B::Message,
+    ) -> Result<TimerHandle, ActorError> {
+        let actor = self.timer_target(actor_id)?;
+
+        Ok(self.timers().schedule(delay, None, move || {
+            let _ = actor.send(message.clone());
+            false
+        }))
+    }
+
+    /// Send `message` to an actor every `interval`, starting one `interval` from now,
+    /// until the timer is cancelled or the actor is removed or stopped.
+    /// Deliveries missed while the timer thread was busy are skipped rather than sent in a burst.
+    pub fn send_interval(
+        &self,
+        actor_id: usize,
+        interval: Duration,
+        message: B::Message,
+    ) -> Result<TimerHandle, ActorError> {
+        if interval.is_zero() {
+            return Err(ActorError::InvalidOperation(
+                "a timer interval can't be zero".to_string(),
+            ));
+        }
+
+        let actor = self.timer_target(actor_id)?;
+
+        Ok(self.timers().schedule(interval, Some(interval), move || {
+            !matches!(
+                actor.send(message.clone()),
+                Err(ActorError::TargetActorNotFound(_) | ActorError::TargetActorIsOffline(_))
+            )
+        }))
+    }
+
+    /// The actor a timer sends to. Sending to a remote actor waits for the network,
+    /// which would hold up every other timer of the pool.
+    fn timer_target(&self, actor_id: usize) -> Result<ActorRef<B>, ActorError> {
+        if self.get_actor_info(actor_id)?.is_remote() {
+            return Err(ActorError::InvalidOperation(format!(
+                "actor {actor_id} is remote and can't be sent timed messages"
+            )));
+        }
+
+        self.actor_ref(actor_id)
+    }
+
+    fn timers(&self) -> &Timers {
+        self.timers.get_or_init(Timers::new)
+    }
+
+    /// Choose how messages sent to the actors of this pool reach their subscribers.
+    /// `Propagation::PerPath` is used by default.
+    pub fn set_propagation(&self, propagation: Propagation) {
+        self.propagation.write().unwrap().mode = propagation;
+    }
+
+    pub fn propagation(&self) -> Propagation {
+        self.propagation.read().unwrap().mode
+    }
+
+    /// Stop propagating messages `max_depth` hops away from the actor they were sent to,
+    /// unless their `Metadata` sets its own limit. `None`, the default, means no limit.
+    pub fn set_max_depth(&self, max_depth: Option<usize>) {
+        self.propagation.write().unwrap().max_depth = max_depth;
+    }
+
+    pub fn max_depth(&self) -> Option<usize> {
+        self.propagation.read().unwrap().max_depth
+    }
+
+    /// Every message that was sent to a missing or stopped actor, rejected or dropped by a full mailbox,
+    /// discarded when its actor stopped, or whose handler failed, oldest first.
+    ///
+    /// Only the last `DEAD_LETTER_CAPACITY` dead letters are kept.
+    pub fn dead_letters(&self) -> Vec<DeadLetter<B::Message>> {
+        self.dead_letters.letters()
+    }
+
+    /// The dead letters that were meant for `actor_id`, oldest first.
+    pub fn dead_letters_for(&self, actor_id: usize) -> Vec<DeadLetter<B::Message>> {
+        let mut letters = self.dead_letters.letters();
+        letters.retain(|letter| letter.target == actor_id);
+
+        letters
+    }
+
+    /// Take every dead letter out of the pool, oldest first.
+    pub fn take_dead_letters(&self) -> Vec<DeadLetter<B::Message>> {
+        self.dead_letters.take()
+    }
+
+    /// Number of dead letters recorded since the pool was created, including the forgotten ones.
+    pub fn dead_letter_count(&self) -> usize {
+        self.dead_letters.recorded()
+    }
+
+    /// Forward every new dead letter to `actor_id` as a regular message, or stop forwarding with `None`.
+    /// The forwarded message's `Metadata::origin` is the actor it was meant for.
+    pub fn set_dead_letter_actor(&self, actor_id: Option<usize>) -> Result<(), ActorError> {
+        let actor = match actor_id {
+            Some(actor_id) => Some(Arc::downgrade(&self.get_actor_info(actor_id)?)),
+            None => None,
+        };
+
+        self.dead_letters.forward_to(actor);
+
+        Ok(())
+    }
+
+    /// When `reject` is `true`, `subscribe` validates the resulting subscription graph and
+    /// fails with `ActorError::CycleDetected` instead of creating a cycle.
+    pub fn set_reject_cycles(&self, reject: bool) {
+        self.topology.reject_cycles.store(reject, Ordering::SeqCst);
+    }
+
+    /// Add `subscriber_actor_ids` to the subscriber list of `target_actor_id`.
+    ///
+    /// Either every subscriber is added or, on error, the subscription graph is left unchanged.
     pub fn subscribe(
         &self,
         target_actor_id: usize,
         subscriber_actor_ids: Vec<usize>,
-    ) -> Result<Arc<Actor>, ActorError> {
-        let target_actor = self.get_actor_info(target_actor_id).unwrap();
+    ) -> Result<Arc<Actor<B>>, ActorError> {
+        let target_actor = self.get_actor_info(target_actor_id)?;
+        let subscriber_actors = subscriber_actor_ids
+            .iter()
+            .map(|subscriber_actor_id| self.get_actor_info(*subscriber_actor_id))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        self.topology.subscribe(&target_actor, subscriber_actors)?;
+
+        Ok(target_actor)
+    }
+
+    /// Snapshot of the subscription graph: every actor id mapped to the ids of its subscribers.
+    pub fn subscription_graph(&self) -> HashMap<usize, Vec<usize>> {
+        self.topology.graph()
+    }
+
+    /// Compute the strongly connected components of the subscription graph,
+    /// the concrete cycles it contains and a topological order of the condensed graph.
+    pub fn analyze_graph(&self) -> GraphAnalysis {
+        GraphAnalysis::new(&self.subscription_graph())
+    }
+
+    /// One concrete cycle per group of actors that subscribe to each other, e.g. `[1, 2, 3, 1]`.
+    ///
+    /// An empty list means that messages can't loop forever between the actors of the pool.
+    pub fn find_cycles(&self) -> Vec<Vec<usize>> {
+        self.analyze_graph().cycles
+    }
+
+    /// Remove `subscriber_actor_ids` from the subscriber list of `target_actor_id`.
+    ///
+    /// Fails with `ActorError::NotInSubscriberList` if any of them is not a subscriber,
+    /// in which case nothing is removed.
+    pub fn unsubscribe(
+        &self,
+        target_actor_id: usize,
+        subscriber_actor_ids: Vec<usize>,
+    ) -> Result<Arc<Actor<B>>, ActorError> {
+        let _topology = self.topology.lock();
+        let target_actor = self.get_actor_info(target_actor_id)?;
+
+        let mut subs = target_actor.get_subscribers();
+        for subscriber_actor_id in subscriber_actor_ids.iter() {
+            // a subscriber listed twice is no longer in the list the second time
+            let position = subs.iter().position(|id| id == subscriber_actor_id);
+            if let Some(position) = position {
+                subs.swap_remove(position);
+            } else {
+                return Err(ActorError::NotInSubscriberList(
+                    subscriber_actor_id.to_string(),
+                    target_actor_id.to_string(),
+                ));
+            }
+        }
 
-        // Add subscribers to the target actor
         for subscriber_actor_id in subscriber_actor_ids {
-            let subscriber_actor = self.get_actor_info(subscriber_actor_id).unwrap();
-            target_actor.add_subscriber(subscriber_actor).unwrap();
+            target_actor.remove_subscriber(subscriber_actor_id)?;
         }
 
         Ok(target_actor)
     }
 
-    pub fn get_actor_info(&self, actor_id: usize) -> Result<Arc<Actor>, ActorError> {
+    /// Remove every subscriber of `actor_id` and return their ids.
+    pub fn clear_subscribers(&self, actor_id: usize) -> Result<Vec<usize>, ActorError> {
+        let _topology = self.topology.lock();
+        let actor = self.get_actor_info(actor_id)?;
+
+        let mut subs = actor.subs.write().unwrap();
+        let mut removed: Vec<usize> = subs.drain().map(|(id, _)| id).collect();
+        removed.sort();
+
+        Ok(removed)
+    }
+
+    /// Reverse lookup of `get_actor_subscribers`: the ids of the actors `actor_id` is subscribed to.
+    pub fn get_publishers(&self, actor_id: usize) -> Result<Vec<usize>, ActorError> {
+        self.get_actor_info(actor_id)?;
+
+        let mut publishers: Vec<usize> = self
+            .subscription_graph()
+            .into_iter()
+            .filter(|(_, subs)| subs.contains(&actor_id))
+            .map(|(id, _)| id)
+            .collect();
+        publishers.sort();
+
+        Ok(publishers)
+    }
+
+    /// Replace the subscriber lists of several actors at once.
+    ///
+    /// Each entry of `rewiring` is a target actor id with its complete new list of subscribers.
+    /// Every id is validated (and, with `set_reject_cycles`, the resulting graph is checked for cycles)
+    /// before anything changes, so either all targets are rewired or none is.
+    pub fn rewire(&self, rewiring: Vec<(usize, Vec<usize>)>) -> Result<(), ActorError> {
+        let _topology = self.topology.lock();
+
+        let mut targets: HashMap<usize, Arc<Actor<B>>> = HashMap::new();
+        let mut new_subs: Vec<HashMap<usize, Arc<Actor<B>>>> = Vec::new();
+
+        for (target_actor_id, subscriber_actor_ids) in rewiring.iter() {
+            let target_actor = self.get_actor_info(*target_actor_id)?;
+            check_publisher(&target_actor, subscriber_actor_ids.len())?;
+            if targets.insert(*target_actor_id, target_actor).is_some() {
+                return Err(ActorError::InvalidOperation(format!(
+                    "actor {target_actor_id} is rewired more than once"
+                )));
+            }
+
+            let mut subs = HashMap::new();
+            for subscriber_actor_id in subscriber_actor_ids.iter() {
+                let subscriber_actor = self.get_actor_info(*subscriber_actor_id)?;
+                if subs
+                    .insert(*subscriber_actor_id, subscriber_actor)
+                    .is_some()
+                {
+                    return Err(ActorError::ActorAlreadyExists(
+                        subscriber_actor_id.to_string(),
+                    ));
+                }
+            }
+            new_subs.push(subs);
+        }
+
+        if self.topology.reject_cycles.load(Ordering::SeqCst) {
+            let mut graph = self.subscription_graph();
+            for (target_actor_id, _) in rewiring.iter() {
+                graph.insert(*target_actor_id, Vec::new());
+            }
+
+            for (target_actor_id, subscriber_actor_ids) in rewiring.iter() {
+                add_edges_acyclic(&mut graph, *target_actor_id, subscriber_actor_ids)?;
+            }
+        }
+
+        for ((target_actor_id, _), subs) in rewiring.iter().zip(new_subs) {
+            *targets[target_actor_id].subs.write().unwrap() = subs;
+        }
+
+        Ok(())
+    }
+
+    pub fn get_actor_info(&self, actor_id: usize) -> Result<Arc<Actor<B>>, ActorError> {
         let actor_list = self.actor_list.lock().unwrap();
         let actor = actor_list
             .get(&actor_id)
@@ -100,32 +818,91 @@ impl ActorPool {
     }
 
     /// `ActorPool::message_loop` method is used to send a message to a specific actor.
-    pub fn message_loop(&self, actor_id: usize, message: Message) -> Result<(), ActorError> {
-        let actor = self.get_actor_info(actor_id)?;
+    ///
+    /// Fails if the actor refuses the message. Subscribers refusing their copy don't make the send fail,
+    /// their copies are reported as dead letters (see `ActorPool::dead_letters`).
+    pub fn message_loop(&self, actor_id: usize, message: B::Message) -> Result<(), ActorError> {
+        let actor = self.recipient(actor_id, &message)?;
 
         actor.send_message(message)
     }
 
+    /// Like `message_loop`, but with the given `Propagation` instead of the pool's.
+    pub fn message_loop_with(
+        &self,
+        actor_id: usize,
+        message: B::Message,
+        propagation: Propagation,
+    ) -> Result<(), ActorError> {
+        let actor = self.recipient(actor_id, &message)?;
+
+        actor.send_message_with(message, propagation)
+    }
+
+    /// Like `message_loop`, but with the given `Metadata`,
+    /// e.g. to set a depth limit or a trace id for this message only.
+    pub fn message_loop_with_metadata(
+        &self,
+        actor_id: usize,
+        message: B::Message,
+        metadata: Metadata,
+    ) -> Result<(), ActorError> {
+        let actor = self.recipient(actor_id, &message)?;
+
+        actor.send_with_metadata(message, metadata)
+    }
+
+    /// Send a message to a specific actor and get a `Reply` that resolves to
+    /// the actor's value once the message has been handled.
+    ///
+    /// The message is propagated to the subscribers just like `message_loop`,
+    /// but only the target actor answers.
+    pub fn ask(&self, actor_id: usize, message: B::Message) -> Result<Reply<B::State>, ActorError> {
+        let actor = self.recipient(actor_id, &message)?;
+
+        actor.ask(message)
+    }
+
+    /// Look up the actor a message is sent to, recording the message as a dead letter if there is none.
+    fn recipient(
+        &self,
+        actor_id: usize,
+        message: &B::Message,
+    ) -> Result<Arc<Actor<B>>, ActorError> {
+        self.get_actor_info(actor_id)
+            .map_err(|error| self.dead_letters.reject(actor_id, message.clone(), error))
+    }
+
+    /// An algorithm that uses DFS to determine if a cycle is reachable from `actor_id`.
+    ///
+    /// Only an edge back to an actor on the current DFS path closes a cycle;
+    /// reaching an actor again through another path (e.g. a diamond) does not.
     pub fn detect_cycle_dfs(&self, actor_id: usize) -> Result<bool, ActorError> {
         let actor = self.get_actor_info(actor_id)?;
 
         let mut visited = HashSet::new();
+        let mut on_path = HashSet::new();
         let mut stack = Vec::new();
 
         visited.insert(actor_id);
-        stack.push(actor);
-
-        while let Some(curr_actor) = stack.pop() {
-            let subs = curr_actor.get_subscribers();
-
-            for sub in subs {
-                if visited.contains(&sub) {
-                    return Ok(true);
-                }
+        on_path.insert(actor_id);
+        stack.push((actor_id, actor.get_subscribers()));
+
+        while let Some((curr_id, subs)) = stack.last_mut() {
+            let Some(sub) = subs.pop() else {
+                on_path.remove(curr_id);
+                stack.pop();
+                continue;
+            };
+
+            if on_path.contains(&sub) {
+                return Ok(true);
+            }
 
+            if visited.insert(sub) {
                 let sub_actor = self.get_actor_info(sub)?;
-                stack.push(sub_actor);
-                visited.insert(sub);
+                on_path.insert(sub);
+                stack.push((sub, sub_actor.get_subscribers()));
             }
         }
 
@@ -137,218 +914,911 @@ impl ActorPool {
     ///
     /// If there is a circular reference, it returns `true`, otherwise `false`.
     ///
+    /// The actors reachable from `actor_id` are collected level by level, then peeled off
+    /// starting from those without a publisher among them; whatever can't be peeled off is part of a cycle.
+    ///
     /// This algorithm is used to prevent circular references when subscribing to actors
     /// which could cause an infinite loop or actor-value
     pub fn detect_cycle_bfs(&self, actor_id: usize) -> Result<bool, ActorError> {
         let actor = self.get_actor_info(actor_id)?;
 
-        let mut visited = HashSet::new();
+        let mut subscribers: HashMap<usize, Vec<usize>> = HashMap::new();
+        let mut in_degree: HashMap<usize, usize> = HashMap::from([(actor_id, 0)]);
         let mut q = VecDeque::new();
 
-        visited.insert(actor_id);
         q.push_back(actor);
 
         while let Some(curr_actor) = q.pop_front() {
             let subs = curr_actor.get_subscribers();
 
-            for sub in subs {
-                if visited.contains(&sub) {
-                    return Ok(true);
+            for sub in subs.iter() {
+                if !in_degree.contains_key(sub) {
+                    q.push_back(self.get_actor_info(*sub)?);
                 }
+                *in_degree.entry(*sub).or_default() += 1;
+            }
 
-                let sub_actor = self.get_actor_info(sub)?;
-                q.push_back(sub_actor);
-                visited.insert(sub);
+            subscribers.insert(curr_actor.id, subs);
+        }
+
+        let mut q: VecDeque<usize> = in_degree
+            .iter()
+            .filter(|(_, degree)| **degree == 0)
+            .map(|(id, _)| *id)
+            .collect();
+        let mut peeled = 0;
+
+        while let Some(curr_id) = q.pop_front() {
+            peeled += 1;
+
+            for sub in subscribers[&curr_id].iter() {
+                let degree = in_degree.get_mut(sub).unwrap();
+                *degree -= 1;
+
+                if *degree == 0 {
+                    q.push_back(*sub);
+                }
             }
         }
 
-        Ok(false)
+        Ok(peeled < in_degree.len())
     }
 
+    /// An algorithm that uses the topological order of the condensed subscription graph (see `GraphAnalysis`)
+    /// to determine if a cycle is reachable from `actor_id`.
+    ///
+    /// Components are listed publishers first, so a single pass from the component of `actor_id`
+    /// finds every component it reaches; there is a cycle when one of them is cyclic.
+    /// Reaching a component through several paths (e.g. a diamond) is not a cycle.
     pub fn detect_cycle_topological_sort(&self, actor_id: usize) -> Result<bool, ActorError> {
-        // map to store the in-degree of each actor
-        let mut in_degree: HashMap<usize, i32> = HashMap::new();
+        self.get_actor_info(actor_id)?;
 
-        let init_actor = self.get_actor_info(actor_id)?;
-        let init_subs = init_actor.get_subscribers();
-
-        in_degree.insert(actor_id, 0);
+        let analysis = self.analyze_graph();
+        let start = analysis
+            .component_of(actor_id)
+            .ok_or(ActorError::TargetActorNotFound(actor_id.to_string()))?;
+        let cyclic: HashSet<usize> = analysis
+            .cycles
+            .iter()
+            .filter_map(|cycle| analysis.component_of(cycle[0]))
+            .collect();
+
+        let mut reached = vec![false; analysis.components.len()];
+        reached[start] = true;
+
+        for component in start..analysis.components.len() {
+            if !reached[component] {
+                continue;
+            }
+            if cyclic.contains(&component) {
+                return Ok(true);
+            }
 
-        for sub in init_subs {
-            in_degree.insert(sub, 0);
+            for &next in analysis.condensed[component].iter() {
+                reached[next] = true;
+            }
         }
 
-        // calculate the in-degree of each node in the subset
-        for (_, actor) in self.actor_list.lock().unwrap().iter() {
-            let subs = actor.get_subscribers();
+        Ok(false)
+    }
+}
 
-            for sub in subs {
-                if let Some(degree) = in_degree.get_mut(&sub) {
-                    *degree += 1;
-                }
+impl<B: Behavior> Default for ActorPool<B> {
+    fn default() -> Self {
+        ActorPool::with_scheduler(Arc::new(Scheduler::default()))
+    }
+}
 
-                in_degree.insert(sub, 1);
-            }
+impl<B: Behavior> Drop for ActorPool<B> {
+    fn drop(&mut self) {
+        self.shutdown(StopMode::Discard);
+    }
+}
+
+/// The subscription graph of a pool. Shared with the `ActorRef`s of its actors,
+/// so that subscribing through a handle follows the same rules as `ActorPool::subscribe`.
+#[derive(Debug)]
+pub(crate) struct Topology<B: Behavior> {
+    /// The `actor_list` of the pool.
+    actors: Arc<Mutex<HashMap<usize, Arc<Actor<B>>>>>,
+    /// When set, `subscribe` rejects subscriptions that would create a cycle.
+    reject_cycles: AtomicBool,
+    /// Serializes changes of the subscription graph, so that validating and applying them is atomic.
+    changes: Mutex<()>,
+}
+
+impl<B: Behavior> Topology<B> {
+    fn new(actors: Arc<Mutex<HashMap<usize, Arc<Actor<B>>>>>) -> Self {
+        Topology {
+            actors,
+            reject_cycles: AtomicBool::new(false),
+            changes: Mutex::new(()),
         }
+    }
+
+    /// Held while the subscription graph is validated and changed.
+    fn lock(&self) -> MutexGuard<'_, ()> {
+        self.changes.lock().unwrap()
+    }
 
-        // queue to store nodes with in-degree of 0
-        let mut q: VecDeque<usize> = VecDeque::new();
+    fn graph(&self) -> HashMap<usize, Vec<usize>> {
+        let actors = self.actors.lock().unwrap();
 
-        // add nodes with in-degree of 0 to the queue
-        for (id, degree) in in_degree.iter() {
-            if *degree == 0 {
-                q.push_back(*id);
+        actors
+            .iter()
+            .map(|(id, actor)| (*id, actor.get_subscribers()))
+            .collect()
+    }
+
+    /// Add `subscribers` to the subscriber list of `target`.
+    ///
+    /// Either every subscriber is added or, on error, the subscription graph is left unchanged.
+    /// Fails if an actor is no longer part of the pool, if a subscriber is already subscribed or listed twice,
+    /// and, with `reject_cycles`, if the subscriptions would create a cycle.
+    pub(crate) fn subscribe(
+        &self,
+        target: &Arc<Actor<B>>,
+        subscribers: Vec<Arc<Actor<B>>>,
+    ) -> Result<(), ActorError> {
+        let _changes = self.lock();
+        self.check_member(target)?;
+        check_publisher(target, subscribers.len())?;
+
+        for (position, subscriber) in subscribers.iter().enumerate() {
+            self.check_member(subscriber)?;
+
+            let already_subscribed = target.subs.read().unwrap().contains_key(&subscriber.id)
+                || subscribers[..position]
+                    .iter()
+                    .any(|actor| actor.id == subscriber.id);
+            if already_subscribed {
+                return Err(ActorError::ActorAlreadyExists(subscriber.id.to_string()));
             }
         }
 
-        while let Some(curr_id) = q.pop_front() {
-            // get current actor and its subscribers
-            let curr_actor = self.get_actor_info(curr_id)?;
-            let curr_subs = curr_actor.get_subscribers();
-
-            for sub in curr_subs {
-                if let Some(degree) = in_degree.get_mut(&sub) {
-                    *degree -= 1;
-
-                    // if in-degree becomes 0, add the subscriber to the queue
-                    if *degree == 0 {
-                        q.push_back(sub);
-                    }
-                }
-            }
+        if self.reject_cycles.load(Ordering::SeqCst) {
+            let subscriber_ids: Vec<usize> = subscribers.iter().map(|actor| actor.id).collect();
+            add_edges_acyclic(&mut self.graph(), target.id, &subscriber_ids)?;
+        }
+
+        for subscriber in subscribers {
+            target.add_subscriber(subscriber)?;
         }
 
-        Ok(in_degree.values().any(|&degree| degree != 0))
+        Ok(())
     }
-}
 
-impl Default for ActorPool {
-    fn default() -> Self {
-        ActorPool {
-            actor_list: Mutex::new(HashMap::new()),
+    /// Fails unless `actor` is still part of the pool. A handle may outlive its actor,
+    /// whose id may even have been given to a newer actor since.
+    fn check_member(&self, actor: &Arc<Actor<B>>) -> Result<(), ActorError> {
+        match self.actors.lock().unwrap().get(&actor.id) {
+            Some(member) if Arc::ptr_eq(member, actor) => Ok(()),
+            _ => Err(ActorError::TargetActorNotFound(actor.id.to_string())),
         }
     }
 }
 
+/// What `Actor::make_room` did to fit a new message in a mailbox.
+enum Room<B: Behavior> {
+    /// There is room for the new message.
+    Free,
+    /// The oldest message not sent with `ask`, or the oldest message if they all were,
+    /// was dropped to make room for the new one.
+    Evicted(Envelope<B::Message, B::State>),
+    /// There is no room: the new message is dropped.
+    Full,
+}
+
+/// The messages waiting in an actor's mailbox.
+pub type Mailbox<B> = VecDeque<Envelope<<B as Behavior>::Message, <B as Behavior>::State>>;
+
 #[derive(Debug)]
-pub struct Actor {
+pub struct Actor<B: Behavior = Counter> {
     pub id: usize,
     pub state: RwLock<ActorState>,
-    pub value: RwLock<i32>,
-    pub subs: RwLock<HashMap<usize, Arc<Actor>>>,
-    pub mailbox: Mutex<VecDeque<Message>>,
+    pub behavior: B,
+    pub value: RwLock<B::State>,
+    pub subs: RwLock<HashMap<usize, Arc<Actor<B>>>>,
+    pub mailbox: Mutex<Mailbox<B>>,
+    /// The messages received while the actor is paused, replayed when it is resumed.
+    /// Always locked after `mailbox`.
+    pub stash: Mutex<Mailbox<B>>,
+    pub mailbox_config: MailboxConfig,
     pub condvar: Condvar,
+    /// Serializes the lifecycle transitions of the actor, so that a transition and its hooks are never interleaved with another.
+    transitioning: Mutex<()>,
+    /// Set while the actor is queued or running on a worker. Only changed while holding the `mailbox` lock.
+    pub scheduled: AtomicBool,
+    /// The supervisor watching this actor, if any.
+    pub supervisor: RwLock<Option<Arc<Supervisor<B>>>>,
+    /// The supervisor run by this actor for its children, if any.
+    pub supervising: RwLock<Option<Arc<Supervisor<B>>>>,
+    scheduler: Arc<Scheduler>,
+    propagation: Arc<RwLock<Settings>>,
+    pub(crate) dead_letters: Arc<DeadLetterOffice<B>>,
+    registry: Arc<Registry>,
+    /// Set when the actor stands for an actor of another pool, see `ActorPool::remote_actor`.
+    pub(crate) remote: OnceLock<Arc<dyn Link<B>>>,
+    myself: Weak<Actor<B>>,
 }
-impl Actor {
-    /// Create a new actor with unique ID
-    fn new() -> Arc<Self> {
-        const INC: usize = 1;
-        let id = ACTOR_ID.fetch_add(INC, Ordering::SeqCst);
-
-        let actor = Actor {
+impl<B: Behavior> Actor<B> {
+    /// Create a new actor with the given unique ID
+    fn new(
+        id: usize,
+        behavior: B,
+        mailbox_config: MailboxConfig,
+        scheduler: Arc<Scheduler>,
+        propagation: Arc<RwLock<Settings>>,
+        dead_letters: Arc<DeadLetterOffice<B>>,
+        registry: Arc<Registry>,
+    ) -> Arc<Self> {
+        let value = behavior.initial_state();
+
+        Arc::new_cyclic(|myself| Actor {
             id,
-            state: RwLock::new(ActorState::Active),
-            value: RwLock::new(INITIAL_VALUE),
+            state: RwLock::new(ActorState::Starting),
+            behavior,
+            value: RwLock::new(value),
             subs: RwLock::new(HashMap::new()),
-            mailbox: Mutex::new(VecDeque::with_capacity(MAILBOX_CAPACITY)),
+            mailbox: Mutex::new(VecDeque::with_capacity(
+                mailbox_config.capacity.unwrap_or_default(),
+            )),
+            stash: Mutex::new(VecDeque::new()),
+            mailbox_config,
             condvar: Condvar::new(),
-        };
+            transitioning: Mutex::new(()),
+            scheduled: AtomicBool::new(false),
+            supervisor: RwLock::new(None),
+            supervising: RwLock::new(None),
+            scheduler,
+            propagation,
+            dead_letters,
+            registry,
+            remote: OnceLock::new(),
+            myself: Weak::clone(myself),
+        })
+    }
 
-        Arc::new(actor)
+    /// Add a message to the actor's mailbox and propagate it to the subscribers
+    pub fn send_message(&self, message: B::Message) -> Result<(), ActorError> {
+        self.send_with_metadata(message, Metadata::new(self.id))
     }
 
-    /// Add a message to the actor's mailbox
-    pub fn send_message(&self, message: Message) -> Result<(), ActorError> {
-        // Check if the mailbox is full
-        let mut mailbox = self.mailbox.lock().unwrap();
+    /// Like `send_message`, but with the given `Propagation` instead of the pool's.
+    pub fn send_message_with(
+        &self,
+        message: B::Message,
+        propagation: Propagation,
+    ) -> Result<(), ActorError> {
+        self.deliver(Envelope::new(message, Metadata::new(self.id)), propagation)
+    }
 
-        if mailbox.len() >= mailbox.capacity() {
-            return Err(ActorError::MailboxOverflow(self.id.to_string()));
-        }
+    /// Like `send_message`, but with the given `Metadata`.
+    pub fn send_with_metadata(
+        &self,
+        message: B::Message,
+        metadata: Metadata,
+    ) -> Result<(), ActorError> {
+        let propagation = self.propagation.read().unwrap().mode;
 
-        // Store the message in the mailbox when the actor is inactive
-        if self.state.read().unwrap().to_owned() == ActorState::Inactive {
-            self.mailbox.lock().unwrap().push_back(message.clone());
+        self.deliver(Envelope::new(message, metadata), propagation)
+    }
+
+    /// Add a message to the actor's mailbox and return a `Reply` to wait for its result
+    pub fn ask(&self, message: B::Message) -> Result<Reply<B::State>, ActorError> {
+        self.ask_with_metadata(message, Metadata::new(self.id))
+    }
+
+    /// Like `ask`, but with the given `Metadata`.
+    pub(crate) fn ask_with_metadata(
+        &self,
+        message: B::Message,
+        metadata: Metadata,
+    ) -> Result<Reply<B::State>, ActorError> {
+        let (envelope, reply) = Envelope::with_reply(self.id, message, metadata);
+        let propagation = self.propagation.read().unwrap().mode;
+        self.deliver(envelope, propagation)?;
+
+        Ok(reply)
+    }
+
+    fn deliver(
+        &self,
+        mut envelope: Envelope<B::Message, B::State>,
+        propagation: Propagation,
+    ) -> Result<(), ActorError> {
+        if envelope.metadata.max_depth.is_none() {
+            envelope.metadata.max_depth = self.propagation.read().unwrap().max_depth;
         }
 
-        // If the actor is active, process the message immediately
-        mailbox.push_back(message.clone());
+        let plan = match propagation::plan(self, &envelope.metadata, propagation) {
+            Ok(plan) => plan,
+            Err(error) => return Err(self.dead_letters.reject(self.id, envelope.message, error)),
+        };
 
-        self.propagate_message(message)?;
+        let message = envelope.message.clone();
+        let metadata = envelope.metadata;
 
-        // Send a notification via `Condvar` whenever a message is added to the `mailbox`.
-        // Each time a message is added, the `execute_messages` (created via `ActorPool::create_actor`) will be woken up and process the message.
-        self.condvar.notify_all();
+        self.enqueue(envelope)?;
+        plan.deliver(self, &message, &metadata);
 
         Ok(())
     }
 
-    fn propagate_message(&self, message: Message) -> Result<(), ActorError> {
-        let subs = self.subs.read().unwrap();
+    /// Store an envelope in the mailbox, or in the stash while the actor is paused, without propagating it.
+    /// Refused and dropped messages are recorded as dead letters.
+    pub(crate) fn enqueue(
+        &self,
+        envelope: Envelope<B::Message, B::State>,
+    ) -> Result<(), ActorError> {
+        let mailbox = self.mailbox.lock().unwrap();
+
+        if self.is_paused() {
+            let refused = self.stash_envelope(envelope);
+            drop(mailbox);
+
+            return match refused {
+                Some(envelope) => Err(self.dead_letters.reject(
+                    self.id,
+                    envelope.message,
+                    ActorError::StashOverflow(self.id.to_string()),
+                )),
+                None => Ok(()),
+            };
+        }
+
+        if let Some(link) = self.remote.get() {
+            drop(mailbox);
+            return self.forward(link.as_ref(), envelope);
+        }
+
+        let (mut mailbox, room) = match self.make_room(mailbox) {
+            Ok(admission) => admission,
+            Err(error) => return Err(self.dead_letters.reject(self.id, envelope.message, error)),
+        };
+
+        let dropped = match room {
+            Room::Full => Some(envelope),
+            Room::Evicted(oldest) => {
+                self.store(&mut mailbox, envelope);
+                Some(oldest)
+            }
+            Room::Free => {
+                self.store(&mut mailbox, envelope);
+                None
+            }
+        };
+
+        // release the mailbox before recording, as the dead letter may be forwarded to another actor
+        drop(mailbox);
 
-        for (_, actor) in subs.iter() {
-            actor.send_message(message.clone())?;
+        if let Some(dropped) = dropped {
+            // the asking side learns why it gets no answer
+            if let Some(reply_to) = dropped.reply_to {
+                let _ = reply_to.send(Err(ActorError::MailboxOverflow(self.id.to_string())));
+            }
+            self.dead_letters
+                .record(self.id, dropped.message, DeadLetterReason::Dropped);
         }
 
         Ok(())
     }
 
-    /// The `execute_messages` method performs an infinite loop,
-    /// and when the `mailbox` is empty, `Condvar`(in here, `self.condvar`) waits for another message.
+    /// Hand an envelope to the remote actor this actor stands for, instead of storing it.
+    /// Asking a remote actor blocks until it has answered.
+    fn forward(
+        &self,
+        link: &dyn Link<B>,
+        envelope: Envelope<B::Message, B::State>,
+    ) -> Result<(), ActorError> {
+        let Envelope {
+            message,
+            metadata,
+            reply_to,
+        } = envelope;
+
+        if self.is_stopped() {
+            let error = ActorError::TargetActorIsOffline(self.id.to_string());
+            return Err(self.dead_letters.reject(self.id, message, error));
+        }
+
+        let delivered = match reply_to {
+            None => link.forward(&message, &metadata),
+            Some(reply_to) => link.ask(&message, &metadata).map(|answer| {
+                let _ = reply_to.send(answer);
+            }),
+        };
+
+        delivered.map_err(|error| self.dead_letters.reject(self.id, message, error))
+    }
+
+    fn store(&self, mailbox: &mut Mailbox<B>, envelope: Envelope<B::Message, B::State>) {
+        mailbox.push_back(envelope);
+
+        // An idle actor is put on the run queue as soon as a message arrives;
+        // a scheduled actor will find the message in its mailbox during its current or next activation.
+        if !self.scheduled.swap(true, Ordering::SeqCst) {
+            self.schedule();
+        }
+    }
+
+    /// Put an envelope in the stash, or hand it back when the stash is full.
+    /// Must be called while holding the `mailbox` lock.
+    fn stash_envelope(
+        &self,
+        envelope: Envelope<B::Message, B::State>,
+    ) -> Option<Envelope<B::Message, B::State>> {
+        let mut stash = self.stash.lock().unwrap();
+
+        if self
+            .mailbox_config
+            .stash_limit
+            .is_some_and(|limit| stash.len() >= limit)
+        {
+            return Some(envelope);
+        }
+
+        stash.push_back(envelope);
+
+        None
+    }
+
+    /// Apply the `OverflowPolicy` when the mailbox is full.
+    /// Returns the mailbox together with whether and how the new message fits in.
+    fn make_room<'a>(
+        &self,
+        mut mailbox: MutexGuard<'a, Mailbox<B>>,
+    ) -> Result<(MutexGuard<'a, Mailbox<B>>, Room<B>), ActorError> {
+        if self.is_stopped() {
+            return Err(ActorError::TargetActorIsOffline(self.id.to_string()));
+        }
+
+        if !self.mailbox_config.is_full(mailbox.len()) {
+            return Ok((mailbox, Room::Free));
+        }
+
+        match self.mailbox_config.overflow {
+            OverflowPolicy::Reject => Err(ActorError::MailboxOverflow(self.id.to_string())),
+            OverflowPolicy::DropOldest => {
+                // an `ask` is only evicted when every waiting message is one, see `enqueue`
+                let oldest = mailbox
+                    .iter()
+                    .position(|envelope| envelope.reply_to.is_none())
+                    .unwrap_or(0);
+                let room = mailbox.remove(oldest).map_or(Room::Free, Room::Evicted);
+                Ok((mailbox, room))
+            }
+            OverflowPolicy::DropNewest => Ok((mailbox, Room::Full)),
+            OverflowPolicy::Block(timeout) => {
+                let deadline = timeout.map(|timeout| Instant::now() + timeout);
+
+                // every message taken out of the mailbox notifies the `condvar`
+                while self.mailbox_config.is_full(mailbox.len()) && !self.is_stopped() {
+                    mailbox = match deadline {
+                        None => self.condvar.wait(mailbox).unwrap(),
+                        Some(deadline) => {
+                            let now = Instant::now();
+                            if now >= deadline {
+                                return Err(ActorError::MailboxOverflow(self.id.to_string()));
+                            }
+
+                            self.condvar
+                                .wait_timeout(mailbox, deadline - now)
+                                .unwrap()
+                                .0
+                        }
+                    };
+                }
+
+                if self.is_stopped() {
+                    return Err(ActorError::TargetActorIsOffline(self.id.to_string()));
+                }
+
+                Ok((mailbox, Room::Free))
+            }
+        }
+    }
+
+    fn schedule(&self) {
+        if let Some(actor) = self.myself.upgrade() {
+            self.scheduler.schedule(move || actor.execute_messages());
+        }
+    }
+
+    /// The `execute_messages` method is one activation of the actor on a worker of the `Scheduler`.
     ///
-    /// When a message is added to the mailbox, `Condvar` is notified and processes the message.
-    /// This allows each actor to continuously process messages in their own thread.
+    /// It consumes at most `Scheduler::budget` messages from the mailbox. If there are messages left,
+    /// the actor goes to the back of the run queue so that other actors get their turn;
+    /// otherwise it becomes idle until the next message arrives.
     fn execute_messages(&self) {
-        loop {
+        for _ in 0..self.scheduler.budget() {
+            let envelope = match self.next_envelope() {
+                Some(envelope) => envelope,
+                None => break,
+            };
+
+            // there is room in the mailbox again for senders blocked by `OverflowPolicy::Block`
+            self.condvar.notify_all();
+
+            if !self.process(envelope) {
+                break;
+            }
+        }
+
+        self.finish_activation();
+    }
+
+    /// Take the next message out of the mailbox. A paused actor doesn't handle messages:
+    /// the ones that reached its mailbox anyway (e.g. a sender blocked by `OverflowPolicy::Block`) are stashed.
+    fn next_envelope(&self) -> Option<Envelope<B::Message, B::State>> {
+        let mut mailbox = self.mailbox.lock().unwrap();
+
+        if self.is_paused() {
+            self.stash.lock().unwrap().extend(mailbox.drain(..));
+            return None;
+        }
+
+        mailbox.pop_front()
+    }
+
+    /// Handle one message, recording it as a dead letter and reporting the failure when the handler fails.
+    /// Returns `false` when the failure stopped the actor.
+    fn process(&self, envelope: Envelope<B::Message, B::State>) -> bool {
+        let message = envelope.message.clone();
+        let asked = envelope.reply_to.is_some();
+
+        // Consume the message without holding the mailbox lock, so that senders are never blocked by a handler
+        let outcome = panic::catch_unwind(AssertUnwindSafe(|| self.handle_envelope(envelope)));
+
+        let (error, panicked) = match outcome {
+            Ok(Ok(())) => return true,
+            Ok(Err(error)) => (error, false),
+            Err(payload) => {
+                // the handler panicked while holding the value lock; the supervisor decides what the value becomes
+                self.value.clear_poison();
+                let error = ActorError::ActorPanicked(panic_message(payload.as_ref()));
+                (error, true)
+            }
+        };
+
+        self.dead_letters.record(
+            self.id,
+            message,
+            DeadLetterReason::HandlerFailed(error.clone()),
+        );
+
+        // a failed `ask` is reported to the asking side, not to the supervisor
+        if asked && !panicked {
+            return true;
+        }
+
+        self.fail(error);
+
+        !self.is_stopped()
+    }
+
+    /// Become idle when the mailbox is empty, otherwise go to the back of the run queue.
+    /// A stopping actor is stopped once it is idle.
+    fn finish_activation(&self) {
+        let mailbox = self.mailbox.lock().unwrap();
+
+        if mailbox.is_empty() {
+            self.scheduled.store(false, Ordering::SeqCst);
+            // wake up anyone waiting in `wait_idle`
+            self.condvar.notify_all();
+            drop(mailbox);
+
+            self.finish_stopping();
+        } else {
+            drop(mailbox);
+            self.schedule();
+        }
+    }
+
+    fn is_paused(&self) -> bool {
+        *self.state.read().unwrap() == ActorState::Paused
+    }
+
+    /// Returns `true` if the actor stands for an actor of another pool.
+    pub(crate) fn is_remote(&self) -> bool {
+        self.remote.get().is_some()
+    }
+
+    /// Move the actor to `to`, calling the `Behavior`'s hooks around the change.
+    /// Returns the state the actor was in.
+    pub(crate) fn transition(&self, to: ActorState) -> Result<ActorState, ActorError> {
+        self.transition_with(to, |_, _| ()).map(|(from, _)| from)
+    }
+
+    /// Like `transition`, but `apply` is also given the mailbox and the stash,
+    /// and runs while both are locked, right after the state changed.
+    fn transition_with<R>(
+        &self,
+        to: ActorState,
+        apply: impl FnOnce(&mut Mailbox<B>, &mut Mailbox<B>) -> R,
+    ) -> Result<(ActorState, R), ActorError> {
+        let _transitioning = self.transitioning.lock().unwrap();
+
+        let from = self.get_state()?;
+        if !from.can_transition_to(to) {
+            return Err(ActorError::InvalidOperation(format!(
+                "actor {} can't go from {from} to {to}",
+                self.id
+            )));
+        }
+
+        self.behavior.pre_transition(self.id, from, to);
+
+        let applied = {
             let mut mailbox = self.mailbox.lock().unwrap();
+            *self.state.write().unwrap() = to;
+
+            let applied = apply(&mut mailbox, &mut self.stash.lock().unwrap());
+
+            // wake up senders blocked on a full mailbox, as there may be room again or the actor is offline
+            self.condvar.notify_all();
+
+            applied
+        };
+
+        // a stopped actor can't be reached by its name anymore, which is free for another actor
+        if to.is_offline() {
+            self.registry.unregister_actor(self.id);
+        }
+
+        self.behavior.post_transition(self.id, from, to);
+
+        Ok((from, applied))
+    }
+
+    /// Stop handling messages: from now on they are stashed, together with the ones waiting in the mailbox.
+    pub(crate) fn pause(&self) -> Result<(), ActorError> {
+        self.transition_with(ActorState::Paused, |mailbox, stash| {
+            stash.extend(mailbox.drain(..))
+        })?;
+
+        Ok(())
+    }
+
+    /// Handle messages again, starting with the stashed ones.
+    ///
+    /// The stash goes back to the front of the mailbox, ahead of any message sent afterwards,
+    /// and an idle actor is scheduled to handle it on a worker, like a newly arrived message.
+    /// An actor standing for a remote actor forwards its stash on the calling thread instead.
+    pub(crate) fn resume(&self) -> Result<(), ActorError> {
+        if self.get_state()? != ActorState::Paused {
+            return Err(ActorError::InvalidOperation(format!(
+                "actor {} isn't paused",
+                self.id
+            )));
+        }
+
+        let (_, stash) = self.transition_with(ActorState::Active, |mailbox, stash| {
+            let mut stash = std::mem::take(stash);
+
+            // a remote actor's messages are never scheduled, the resuming thread forwards them
+            if stash.is_empty() || self.is_remote() {
+                return stash;
+            }
 
-            // When the mailbox is empty, the `condvar` will wait for a notification
-            if mailbox.is_empty() {
-                mailbox = self.condvar.wait(mailbox).unwrap();
+            while let Some(envelope) = stash.pop_back() {
+                mailbox.push_front(envelope);
             }
 
-            // Consume messages in the mailbox
-            while let Some(msg) = mailbox.pop_front() {
-                self.handle_message(msg).unwrap();
+            if !self.scheduled.swap(true, Ordering::SeqCst) {
+                self.schedule();
+            }
+
+            stash
+        })?;
+
+        if let Some(link) = self.remote.get() {
+            self.forward_stash(link.as_ref(), stash);
+        }
+
+        Ok(())
+    }
+
+    /// Forward the stash of a resumed remote actor. The messages left when the actor is paused
+    /// again go back to the stash, ahead of newer messages; they are discarded when it stopped.
+    fn forward_stash(&self, link: &dyn Link<B>, mut stash: Mailbox<B>) {
+        while let Some(envelope) = stash.pop_front() {
+            if self.is_paused() || self.is_stopped() {
+                stash.push_front(envelope);
+                break;
+            }
+
+            // a message the remote actor can't get is recorded as a dead letter by `forward`
+            let _ = self.forward(link, envelope);
+        }
+
+        if stash.is_empty() {
+            return;
+        }
+
+        let mailbox = self.mailbox.lock().unwrap();
+
+        if self.is_stopped() {
+            drop(mailbox);
+
+            let dropped: Vec<B::Message> =
+                stash.into_iter().map(|envelope| envelope.message).collect();
+            self.discard(&dropped);
+        } else {
+            let mut newer = self.stash.lock().unwrap();
+            while let Some(envelope) = stash.pop_back() {
+                newer.push_front(envelope);
             }
         }
     }
 
-    /// Handles a message by matching its type and calling the appropriate handler
-    pub fn handle_message(&self, message: Message) -> Result<(), ActorError> {
-        let result = match message {
-            Message::Increment(n) => self.increment(n),
-            Message::Decrement(n) => self.decrement(n),
+    /// Take the stashed messages out, recording them as dead letters.
+    pub(crate) fn discard_stash(&self) -> Vec<B::Message> {
+        let mailbox = self.mailbox.lock().unwrap();
+        let dropped: Vec<B::Message> = self
+            .stash
+            .lock()
+            .unwrap()
+            .drain(..)
+            .map(|envelope| envelope.message)
+            .collect();
+        drop(mailbox);
+
+        self.discard(&dropped);
+
+        dropped
+    }
+
+    /// Returns `true` once the actor is stopping, stopped or failed: it never accepts messages again.
+    pub fn is_stopped(&self) -> bool {
+        self.state.read().unwrap().is_offline()
+    }
+
+    /// Report a failed handler to the supervisor, which restarts or stops the actor.
+    /// Without a supervisor the actor is brought down: it becomes `ActorState::Failed` and its remaining messages are dropped.
+    pub(crate) fn fail(&self, error: ActorError) {
+        let supervisor = self.supervisor.read().unwrap().clone();
+
+        match supervisor {
+            Some(supervisor) => supervisor.child_failed(self.id, error),
+            None => {
+                // an actor that is already stopped or failed has nothing left to drop
+                if let Ok((_, dropped)) =
+                    self.transition_with(ActorState::Failed, |mailbox, stash| drain(stash, mailbox))
+                {
+                    self.discard(&dropped);
+                }
+            }
+        }
+    }
+
+    /// Replace the actor's value with a fresh state, going through `ActorState::Restarting`
+    /// and back to the state the actor was in.
+    pub(crate) fn restart(&self, state: B::State) -> Result<(), ActorError> {
+        let previous = self.transition(ActorState::Restarting)?;
+        *self.value.write().unwrap() = state;
+
+        self.transition(previous)?;
+
+        Ok(())
+    }
+
+    /// Mark the actor as stopping, so that it doesn't accept messages anymore.
+    /// With `StopMode::Discard` the pending and stashed messages are taken out and returned.
+    ///
+    /// The actor is stopped as soon as it is idle. Stopping an actor that is already stopping,
+    /// stopped or failed does nothing.
+    pub(crate) fn stop(&self, mode: StopMode) -> Vec<B::Message> {
+        let stopping = self.transition_with(ActorState::Stopping, |mailbox, stash| match mode {
+            StopMode::Drain => Vec::new(),
+            StopMode::Discard => drain(stash, mailbox),
+        });
+
+        let dropped = match stopping {
+            Ok((_, dropped)) => dropped,
+            Err(_) => return Vec::new(),
         };
 
-        // using condvar to notify the `execute_messages` thread that the message has been processed.
+        self.discard(&dropped);
+
+        // a running actor is stopped by its worker, at the end of its activation
+        if !self.scheduled.load(Ordering::SeqCst) {
+            self.finish_stopping();
+        }
+
+        dropped
+    }
+
+    /// Move a stopping actor to `ActorState::Stopped`.
+    fn finish_stopping(&self) {
+        if *self.state.read().unwrap() == ActorState::Stopping {
+            // the actor may be stopped by its worker and by the pool at the same time
+            let _ = self.transition(ActorState::Stopped);
+        }
+    }
+
+    /// Block until the actor is neither queued nor running on a worker.
+    fn wait_idle(&self) {
+        let mut mailbox = self.mailbox.lock().unwrap();
+
+        while self.scheduled.load(Ordering::SeqCst) {
+            mailbox = self.condvar.wait(mailbox).unwrap();
+        }
+    }
+
+    /// Take every message left in the stash and the mailbox.
+    fn take_mailbox(&self) -> Vec<B::Message> {
+        let mut mailbox = self.mailbox.lock().unwrap();
         self.condvar.notify_all();
 
+        let dropped = drain(&mut self.stash.lock().unwrap(), &mut mailbox);
+        drop(mailbox);
+
+        self.discard(&dropped);
+
+        dropped
+    }
+
+    /// Record messages that will never be handled as dead letters.
+    fn discard(&self, messages: &[B::Message]) {
+        for message in messages {
+            self.dead_letters
+                .record(self.id, message.clone(), DeadLetterReason::Discarded);
+        }
+    }
+
+    /// Handles a message and, when it was sent with `ask`, sends the result back to the asking side.
+    fn handle_envelope(&self, envelope: Envelope<B::Message, B::State>) -> Result<(), ActorError> {
+        let Envelope {
+            message,
+            metadata,
+            reply_to,
+        } = envelope;
+        let mut value = self.value.write().unwrap();
+        let result = self
+            .behavior
+            .handle_with_metadata(&mut value, message, &metadata);
+
+        // the asking side may have given up waiting, which is not an error for the actor.
+        if let Some(reply_to) = reply_to {
+            let _ = reply_to.send(result.clone().map(|_| value.clone()));
+        }
+
         result
     }
 
+    /// Handles a message by passing it to the actor's `Behavior` together with its current value
+    pub fn handle_message(&self, message: B::Message) -> Result<(), ActorError> {
+        let mut value = self.value.write().unwrap();
+        self.behavior.handle(&mut value, message)
+    }
+
     fn get_id(&self) -> usize {
         self.id
     }
 
-    fn get_state(&self) -> Result<ActorState, ActorError> {
+    pub(crate) fn get_state(&self) -> Result<ActorState, ActorError> {
         let value = self.state.read().unwrap();
 
         Ok(value.to_owned())
     }
 
-    fn get_value(&self) -> Result<i32, ActorError> {
-        let value = self.value.read().unwrap();
-
-        Ok(value.to_owned())
+    /// The state reported outside of the actor: the state of the remote actor for an online proxy.
+    pub(crate) fn reported_state(&self) -> Result<ActorState, ActorError> {
+        match self.remote.get() {
+            Some(link) if !self.is_stopped() => link.state(),
+            _ => self.get_state(),
+        }
     }
 
-    //TODO:  I think this method chance return type to `Result<i32, ActorError>` is better.
-    fn set_value(&self, value: i32) -> Result<(), ActorError> {
-        let mut value_lock = self.value.write().unwrap();
-        *value_lock = value;
+    pub(crate) fn get_value(&self) -> Result<B::State, ActorError> {
+        if let Some(link) = self.remote.get() {
+            return link.value();
+        }
 
-        Ok(())
+        let value = self.value.read().unwrap();
+
+        Ok(value.to_owned())
     }
 
     pub fn get_subscribers(&self) -> Vec<usize> {
@@ -356,7 +1826,10 @@ impl Actor {
         subs.keys().cloned().collect()
     }
 
-    fn add_subscriber(&self, actor: Arc<Actor>) -> Result<Option<Arc<Actor>>, ActorError> {
+    pub(crate) fn add_subscriber(
+        &self,
+        actor: Arc<Actor<B>>,
+    ) -> Result<Option<Arc<Actor<B>>>, ActorError> {
         let mut subs = self.subs.write().unwrap();
 
         if subs.contains_key(&actor.get_id()) {
@@ -366,33 +1839,49 @@ impl Actor {
         Ok(subs.insert(actor.get_id(), actor))
     }
 
-    fn remove_subscriber(&self, actor_id: usize) -> Result<(), ActorError> {
+    pub(crate) fn remove_subscriber(&self, actor_id: usize) -> Result<Arc<Actor<B>>, ActorError> {
         let mut subs = self.subs.write().unwrap();
 
-        if subs.contains_key(&actor_id) {
-            subs.remove(&actor_id);
-        }
-
-        Err(ActorError::TargetActorNotFound(actor_id.to_string()))
+        subs.remove(&actor_id)
+            .ok_or(ActorError::NotInSubscriberList(
+                actor_id.to_string(),
+                self.id.to_string(),
+            ))
     }
+}
 
-    // Message handlers
-
-    fn update_value<F>(&self, modifier: F) -> Result<(), ActorError>
-    where
-        F: FnOnce(i32) -> Result<i32, ActorError>,
-    {
-        let mut value = self.get_value()?;
-        value = modifier(value)?;
-
-        self.set_value(value)
+/// Fails if `subscribers` actors can't be subscribed to `target`: the messages a remote actor
+/// receives in its own pool never reach the reference standing for it, so neither would they reach its subscribers.
+fn check_publisher<B: Behavior>(target: &Actor<B>, subscribers: usize) -> Result<(), ActorError> {
+    if subscribers > 0 && target.is_remote() {
+        return Err(ActorError::InvalidOperation(format!(
+            "actor {} stands for a remote actor, which can't be subscribed to",
+            target.id
+        )));
     }
 
-    fn increment(&self, n: i32) -> Result<(), ActorError> {
-        self.update_value(|value| Ok(value + n))
-    }
+    Ok(())
+}
+
+/// Take the messages out of the stash, then out of the mailbox.
+fn drain<M, S>(
+    stash: &mut VecDeque<Envelope<M, S>>,
+    mailbox: &mut VecDeque<Envelope<M, S>>,
+) -> Vec<M> {
+    stash
+        .drain(..)
+        .chain(mailbox.drain(..))
+        .map(|envelope| envelope.message)
+        .collect()
+}
 
-    fn decrement(&self, n: i32) -> Result<(), ActorError> {
-        self.update_value(|value| Ok(value - n))
+/// Turn the payload of a caught panic into a readable message.
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
     }
 }
@@ -0,0 +1,198 @@
+use std::{
+    fmt,
+    hash::{Hash, Hasher},
+    sync::{Arc, Weak},
+};
+
+use super::{
+    actor::{Actor, Topology},
+    behavior::{Behavior, Counter},
+    dead_letter::DeadLetterOffice,
+    envelope::{Metadata, Reply},
+    errors::ActorError,
+    state::ActorState,
+};
+
+/// `ActorRef` is a cloneable handle to a single actor of a specific `ActorPool`.
+///
+/// It talks to the actor directly instead of going through the pool's `actor_list`,
+/// so sending a message or reading the state never contends on the pool lock.
+/// The handle does not keep the actor alive: once the actor has been removed from its pool,
+/// every operation returns `ActorError::TargetActorNotFound`, and messages sent to it become dead letters of the pool.
+pub struct ActorRef<B: Behavior = Counter> {
+    id: usize,
+    pool_id: usize,
+    generation: u64,
+    actor: Weak<Actor<B>>,
+    dead_letters: Weak<DeadLetterOffice<B>>,
+    topology: Weak<Topology<B>>,
+}
+
+impl<B: Behavior> ActorRef<B> {
+    pub(crate) fn new(
+        pool_id: usize,
+        generation: u64,
+        actor: &Arc<Actor<B>>,
+        topology: &Arc<Topology<B>>,
+    ) -> Self {
+        ActorRef {
+            id: actor.id,
+            pool_id,
+            generation,
+            actor: Arc::downgrade(actor),
+            dead_letters: Arc::downgrade(&actor.dead_letters),
+            topology: Arc::downgrade(topology),
+        }
+    }
+
+    /// The id of the referenced actor inside its pool.
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    /// The id of the pool the referenced actor belongs to.
+    pub fn pool_id(&self) -> usize {
+        self.pool_id
+    }
+
+    /// The generation of the referenced actor's id, which tells it apart from other actors
+    /// that had the same id before it (see `ActorPool::set_id_reuse`).
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Returns `true` while the referenced actor still exists.
+    pub fn is_alive(&self) -> bool {
+        self.actor.strong_count() > 0
+    }
+
+    /// Send a message straight into the actor's mailbox.
+    /// The message is propagated to the actor's subscribers as with `ActorPool::message_loop`.
+    pub fn send(&self, message: B::Message) -> Result<(), ActorError> {
+        self.recipient(&message)?.send_message(message)
+    }
+
+    /// Like `send`, but with the given `Metadata`.
+    pub fn send_with_metadata(
+        &self,
+        message: B::Message,
+        metadata: Metadata,
+    ) -> Result<(), ActorError> {
+        self.recipient(&message)?
+            .send_with_metadata(message, metadata)
+    }
+
+    /// Send a message straight into the actor's mailbox and get a `Reply` to wait for its result.
+    pub fn ask(&self, message: B::Message) -> Result<Reply<B::State>, ActorError> {
+        self.recipient(&message)?.ask(message)
+    }
+
+    pub fn state(&self) -> Result<ActorState, ActorError> {
+        self.upgrade()?.reported_state()
+    }
+
+    pub fn value(&self) -> Result<B::State, ActorError> {
+        self.upgrade()?.get_value()
+    }
+
+    pub fn subscribers(&self) -> Result<Vec<usize>, ActorError> {
+        Ok(self.upgrade()?.get_subscribers())
+    }
+
+    /// Add `subscribers` to the subscriber list of this actor, as `ActorPool::subscribe` does.
+    ///
+    /// Either every subscriber is added or, on error, nothing is subscribed: every subscriber must
+    /// belong to the same pool as this actor (`ActorError::InvalidOperation`), still exist
+    /// (`ActorError::TargetActorNotFound`), and be neither subscribed already nor listed twice
+    /// (`ActorError::ActorAlreadyExists`). With `ActorPool::set_reject_cycles`, a subscription
+    /// that would create a cycle fails with `ActorError::CycleDetected`.
+    pub fn subscribe(&self, subscribers: &[ActorRef<B>]) -> Result<(), ActorError> {
+        let target = self.upgrade()?;
+
+        let mut subscriber_actors = Vec::with_capacity(subscribers.len());
+        for subscriber in subscribers {
+            self.check_same_pool(subscriber)?;
+            subscriber_actors.push(subscriber.upgrade()?);
+        }
+
+        // the pool is gone along with its actors
+        let topology = self
+            .topology
+            .upgrade()
+            .ok_or(ActorError::TargetActorNotFound(self.id.to_string()))?;
+
+        topology.subscribe(&target, subscriber_actors)
+    }
+
+    pub(crate) fn upgrade(&self) -> Result<Arc<Actor<B>>, ActorError> {
+        self.actor
+            .upgrade()
+            .ok_or(ActorError::TargetActorNotFound(self.id.to_string()))
+    }
+
+    /// Like `upgrade`, but records `message` as a dead letter when the actor is gone.
+    fn recipient(&self, message: &B::Message) -> Result<Arc<Actor<B>>, ActorError> {
+        self.upgrade()
+            .map_err(|error| match self.dead_letters.upgrade() {
+                Some(dead_letters) => dead_letters.reject(self.id, message.clone(), error),
+                None => error,
+            })
+    }
+
+    pub(crate) fn check_same_pool(&self, other: &ActorRef<B>) -> Result<(), ActorError> {
+        if self.pool_id != other.pool_id {
+            return Err(ActorError::InvalidOperation(format!(
+                "actor {} of pool {} and actor {} of pool {} belong to different pools",
+                self.id, self.pool_id, other.id, other.pool_id
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+impl<B: Behavior> Clone for ActorRef<B> {
+    fn clone(&self) -> Self {
+        ActorRef {
+            id: self.id,
+            pool_id: self.pool_id,
+            generation: self.generation,
+            actor: Weak::clone(&self.actor),
+            dead_letters: Weak::clone(&self.dead_letters),
+            topology: Weak::clone(&self.topology),
+        }
+    }
+}
+
+impl<B: Behavior> PartialEq for ActorRef<B> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id && self.pool_id == other.pool_id && self.generation == other.generation
+    }
+}
+
+impl<B: Behavior> Eq for ActorRef<B> {}
+
+impl<B: Behavior> Hash for ActorRef<B> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+        self.pool_id.hash(state);
+        self.generation.hash(state);
+    }
+}
+
+impl<B: Behavior> fmt::Debug for ActorRef<B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ActorRef")
+            .field("id", &self.id)
+            .field("pool_id", &self.pool_id)
+            .field("generation", &self.generation)
+            .field("alive", &self.is_alive())
+            .finish()
+    }
+}
+
+impl<B: Behavior> fmt::Display for ActorRef<B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}@{}", self.id, self.pool_id)
+    }
+}
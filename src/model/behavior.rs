@@ -0,0 +1,78 @@
+use std::fmt::Debug;
+
+use super::{envelope::Metadata, errors::ActorError, message::Message, state::ActorState};
+
+pub static INITIAL_VALUE: i32 = 0;
+
+/// `Behavior` describes what an actor owns and how it reacts to the messages it receives.
+///
+/// Every actor in an `ActorPool` is driven by one behavior instance. The pool only ever
+/// talks to the actor through `Behavior::State` and `Behavior::Message`, so any state struct
+/// and message enum can be used as long as they can be shared between threads.
+pub trait Behavior: Send + Sync + 'static {
+    /// The state owned by a single actor.
+    type State: Clone + Debug + Send + Sync + 'static;
+    /// The messages an actor accepts (and propagates to its subscribers).
+    type Message: Clone + Debug + Send + Sync + 'static;
+
+    /// Returns the state an actor starts with.
+    fn initial_state(&self) -> Self::State;
+
+    /// Handles a single message by mutating the actor's state in place.
+    fn handle(&self, state: &mut Self::State, message: Self::Message) -> Result<(), ActorError>;
+
+    /// Handles a message knowing where it comes from and how far it has been propagated.
+    /// Defaults to `Behavior::handle`.
+    fn handle_with_metadata(
+        &self,
+        state: &mut Self::State,
+        message: Self::Message,
+        _metadata: &Metadata,
+    ) -> Result<(), ActorError> {
+        self.handle(state, message)
+    }
+
+    /// Called before the actor `actor_id` moves from `from` to `to` (see `ActorState`).
+    /// Transitions are never refused by their hooks, and don't wait for a running handler,
+    /// so hooks get no access to the actor's state.
+    fn pre_transition(&self, _actor_id: usize, _from: ActorState, _to: ActorState) {}
+
+    /// Called once the actor `actor_id` has moved from `from` to `to`.
+    fn post_transition(&self, _actor_id: usize, _from: ActorState, _to: ActorState) {}
+}
+
+/// `Counter` is the built-in behavior: an `i32` value updated with the arithmetic of `Message`.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Counter;
+
+impl Behavior for Counter {
+    type State = i32;
+    type Message = Message;
+
+    fn initial_state(&self) -> i32 {
+        INITIAL_VALUE
+    }
+
+    fn handle(&self, state: &mut i32, message: Message) -> Result<(), ActorError> {
+        let value = *state;
+        let overflow = || ActorError::Overflow(format!("{message} on {value}"));
+
+        *state = match message {
+            Message::Increment(n) => value.checked_add(n).ok_or_else(overflow)?,
+            Message::Decrement(n) => value.checked_sub(n).ok_or_else(overflow)?,
+            Message::Set(n) => n,
+            Message::Reset => INITIAL_VALUE,
+            Message::Multiply(n) => value.checked_mul(n).ok_or_else(overflow)?,
+            Message::Divide(0) | Message::Modulo(0) => return Err(ActorError::DividedByZero),
+            // `i32::MIN / -1` is the only division that overflows
+            Message::Divide(n) => value.checked_div(n).ok_or_else(overflow)?,
+            Message::Modulo(n) => value.checked_rem(n).ok_or_else(overflow)?,
+            Message::Min(n) => value.min(n),
+            Message::Max(n) => value.max(n),
+            Message::CompareAndSet { expected, new } if value == expected => new,
+            Message::CompareAndSet { .. } => value,
+        };
+
+        Ok(())
+    }
+}
@@ -0,0 +1,176 @@
+use std::{
+    collections::VecDeque,
+    fmt,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex, RwLock, Weak,
+    },
+    time::SystemTime,
+};
+
+use super::{actor::Actor, behavior::Behavior, envelope::Metadata, errors::ActorError};
+
+/// The number of dead letters an `ActorPool` keeps; older ones are forgotten first.
+pub static DEAD_LETTER_CAPACITY: usize = 1000;
+
+/// Why a message ended up in the dead-letter office.
+#[derive(Debug, Clone)]
+pub enum DeadLetterReason {
+    /// There was no actor with the target id.
+    ActorNotFound,
+    /// The target actor was stopped.
+    ActorOffline,
+    /// The target's mailbox was full and rejected the message
+    /// (`OverflowPolicy::Reject`, or `OverflowPolicy::Block` with an elapsed timeout).
+    MailboxOverflow,
+    /// The target was paused and its stash was full.
+    StashOverflow,
+    /// The message was dropped from a full mailbox by `OverflowPolicy::DropOldest` or `OverflowPolicy::DropNewest`.
+    Dropped,
+    /// The message was still in the mailbox or stash when the actor stopped, or its stash was discarded,
+    /// and was never handled.
+    Discarded,
+    /// The handler returned an error or panicked.
+    HandlerFailed(ActorError),
+    /// The message was refused with an error that has no reason of its own.
+    Other(ActorError),
+}
+
+/// The reason of a message refused with `error`. Failed handlers are recorded as `DeadLetterReason::HandlerFailed` instead.
+impl From<ActorError> for DeadLetterReason {
+    fn from(error: ActorError) -> Self {
+        match error {
+            ActorError::TargetActorNotFound(_) => DeadLetterReason::ActorNotFound,
+            ActorError::TargetActorIsOffline(_) => DeadLetterReason::ActorOffline,
+            ActorError::MailboxOverflow(_) => DeadLetterReason::MailboxOverflow,
+            ActorError::StashOverflow(_) => DeadLetterReason::StashOverflow,
+            ActorError::ActorAlreadyExists(_)
+            | ActorError::InvalidMessage(_)
+            | ActorError::InvalidOperation(_)
+            | ActorError::NotInSubscriberList(_, _)
+            | ActorError::LockError(_)
+            | ActorError::DividedByZero
+            | ActorError::Overflow(_)
+            | ActorError::Timeout(_)
+            | ActorError::ActorPanicked(_)
+            | ActorError::RestartIntensityExceeded(_)
+            | ActorError::CycleDetected(_)
+            | ActorError::InvalidSnapshot(_)
+            | ActorError::Io(_)
+            | ActorError::AssertionFailed(_) => DeadLetterReason::Other(error),
+        }
+    }
+}
+
+impl fmt::Display for DeadLetterReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            DeadLetterReason::ActorNotFound => write!(f, "actor not found"),
+            DeadLetterReason::ActorOffline => write!(f, "actor offline"),
+            DeadLetterReason::MailboxOverflow => write!(f, "mailbox overflow"),
+            DeadLetterReason::StashOverflow => write!(f, "stash overflow"),
+            DeadLetterReason::Dropped => write!(f, "dropped from a full mailbox"),
+            DeadLetterReason::Discarded => write!(f, "discarded when the actor stopped"),
+            DeadLetterReason::HandlerFailed(ref error) => write!(f, "handler failed: {error}"),
+            DeadLetterReason::Other(ref error) => write!(f, "refused: {error}"),
+        }
+    }
+}
+
+/// A message that was never delivered or handled successfully.
+#[derive(Debug, Clone)]
+pub struct DeadLetter<M> {
+    /// The id of the actor the message was meant for.
+    pub target: usize,
+    pub message: M,
+    pub reason: DeadLetterReason,
+    pub at: SystemTime,
+}
+
+/// `DeadLetterOffice` records the dead letters of an `ActorPool` and is shared by all of its actors.
+///
+/// Dead letters can also be forwarded to an actor of the pool (see `ActorPool::set_dead_letter_actor`),
+/// which receives them as regular messages whose `Metadata::origin` is the original target.
+/// Forwarded letters are not propagated to the subscribers of that actor.
+pub(crate) struct DeadLetterOffice<B: Behavior> {
+    letters: Mutex<VecDeque<DeadLetter<B::Message>>>,
+    recorded: AtomicUsize,
+    forward_to: RwLock<Option<Weak<Actor<B>>>>,
+}
+
+impl<B: Behavior> DeadLetterOffice<B> {
+    pub(crate) fn new() -> Self {
+        DeadLetterOffice {
+            letters: Mutex::new(VecDeque::new()),
+            recorded: AtomicUsize::new(0),
+            forward_to: RwLock::new(None),
+        }
+    }
+
+    pub(crate) fn record(&self, target: usize, message: B::Message, reason: DeadLetterReason) {
+        // the dead letters of the forwarding actor itself are only recorded, so that they can't loop
+        // the lock is released before forwarding, which may record dead letters of its own
+        let forward_to = self.forward_to.read().unwrap().clone();
+        let forward_to = forward_to
+            .as_ref()
+            .and_then(Weak::upgrade)
+            .filter(|actor| actor.id != target);
+
+        if let Some(actor) = forward_to {
+            // forwarded letters are not propagated any further, and a failed forward
+            // becomes a dead letter of the forwarding actor
+            let metadata = Metadata::new(target).with_max_depth(0);
+            let _ = actor.send_with_metadata(message.clone(), metadata);
+        }
+
+        let mut letters = self.letters.lock().unwrap();
+        if letters.len() >= DEAD_LETTER_CAPACITY {
+            letters.pop_front();
+        }
+
+        letters.push_back(DeadLetter {
+            target,
+            message,
+            reason,
+            at: SystemTime::now(),
+        });
+        self.recorded.fetch_add(1, Ordering::SeqCst);
+    }
+
+    /// Record a message refused with `error`, and hand the error back.
+    pub(crate) fn reject(
+        &self,
+        target: usize,
+        message: B::Message,
+        error: ActorError,
+    ) -> ActorError {
+        self.record(target, message, error.clone().into());
+
+        error
+    }
+
+    pub(crate) fn letters(&self) -> Vec<DeadLetter<B::Message>> {
+        self.letters.lock().unwrap().iter().cloned().collect()
+    }
+
+    pub(crate) fn take(&self) -> Vec<DeadLetter<B::Message>> {
+        self.letters.lock().unwrap().drain(..).collect()
+    }
+
+    pub(crate) fn recorded(&self) -> usize {
+        self.recorded.load(Ordering::SeqCst)
+    }
+
+    pub(crate) fn forward_to(&self, actor: Option<Weak<Actor<B>>>) {
+        *self.forward_to.write().unwrap() = actor;
+    }
+}
+
+impl<B: Behavior> fmt::Debug for DeadLetterOffice<B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("DeadLetterOffice")
+            .field("letters", &self.letters.lock().unwrap().len())
+            .field("recorded", &self.recorded())
+            .finish()
+    }
+}
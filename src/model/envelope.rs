@@ -0,0 +1,135 @@
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        mpsc::{self, Receiver, RecvTimeoutError, Sender},
+    },
+    time::Duration,
+};
+
+use super::errors::ActorError;
+
+static TRACE_ID: AtomicU64 = AtomicU64::new(0);
+
+/// `Metadata` travels with a message and every copy of it propagated through the subscription graph.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct Metadata {
+    /// The actor the message was sent to before being propagated.
+    pub origin: usize,
+    /// Number of subscriptions the message went through since `origin`.
+    pub hops: usize,
+    /// The message is not propagated to actors more than `max_depth` hops away from `origin`.
+    /// `None` falls back to the pool's limit (see `ActorPool::set_max_depth`).
+    pub max_depth: Option<usize>,
+    /// Shared by every copy of the message, to follow it through the graph.
+    pub trace_id: u64,
+}
+
+impl Metadata {
+    /// Metadata for a message sent to `origin`, with a new trace id.
+    pub fn new(origin: usize) -> Self {
+        Metadata {
+            origin,
+            hops: 0,
+            max_depth: None,
+            trace_id: TRACE_ID.fetch_add(1, Ordering::SeqCst),
+        }
+    }
+
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Reuse an existing trace id, e.g. to follow a message forwarded by a handler.
+    pub fn with_trace_id(mut self, trace_id: u64) -> Self {
+        self.trace_id = trace_id;
+        self
+    }
+
+    /// The metadata of a copy propagated `distance` subscriptions further.
+    pub(crate) fn forwarded(&self, distance: usize) -> Self {
+        Metadata {
+            hops: self.hops + distance,
+            ..*self
+        }
+    }
+
+    /// Number of subscriptions the message may still go through, or `None` if there is no limit.
+    pub(crate) fn remaining_hops(&self) -> Option<usize> {
+        self.max_depth
+            .map(|max_depth| max_depth.saturating_sub(self.hops))
+    }
+}
+
+/// `Envelope` is what is actually stored in an actor's mailbox.
+///
+/// It wraps the user message together with its `Metadata` and an optional reply channel,
+/// which is only present when the message was sent with `ask`.
+#[derive(Debug)]
+pub struct Envelope<M, R> {
+    pub message: M,
+    pub metadata: Metadata,
+    pub reply_to: Option<Sender<Result<R, ActorError>>>,
+}
+
+impl<M, R> Envelope<M, R> {
+    /// Wrap a fire-and-forget message.
+    pub fn new(message: M, metadata: Metadata) -> Self {
+        Envelope {
+            message,
+            metadata,
+            reply_to: None,
+        }
+    }
+
+    /// Wrap a message that expects an answer, returning the envelope and the handle to wait on.
+    pub fn with_reply(actor_id: usize, message: M, metadata: Metadata) -> (Self, Reply<R>) {
+        let (tx, rx) = mpsc::channel();
+
+        let envelope = Envelope {
+            message,
+            metadata,
+            reply_to: Some(tx),
+        };
+
+        (envelope, Reply { actor_id, rx })
+    }
+}
+
+/// `Reply` is returned by `ask` and resolves to the actor's value once the message has been handled,
+/// or to the `ActorError` returned by the handler.
+#[derive(Debug)]
+pub struct Reply<R> {
+    actor_id: usize,
+    rx: Receiver<Result<R, ActorError>>,
+}
+
+impl<R> Reply<R> {
+    /// The id of the actor that will answer.
+    pub fn actor_id(&self) -> usize {
+        self.actor_id
+    }
+
+    /// Block until the actor has handled the message.
+    pub fn wait(self) -> Result<R, ActorError> {
+        self.rx
+            .recv()
+            .map_err(|_| ActorError::TargetActorIsOffline(self.actor_id.to_string()))?
+    }
+
+    /// Block until the actor has handled the message or `timeout` elapses.
+    pub fn wait_timeout(self, timeout: Duration) -> Result<R, ActorError> {
+        match self.rx.recv_timeout(timeout) {
+            Ok(result) => result,
+            Err(RecvTimeoutError::Timeout) => Err(ActorError::Timeout(self.actor_id.to_string())),
+            Err(RecvTimeoutError::Disconnected) => {
+                Err(ActorError::TargetActorIsOffline(self.actor_id.to_string()))
+            }
+        }
+    }
+
+    /// Return the answer if it has already arrived, without blocking.
+    pub fn try_wait(&self) -> Option<Result<R, ActorError>> {
+        self.rx.try_recv().ok()
+    }
+}
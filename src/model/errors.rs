@@ -1,7 +1,9 @@
 use std::error::Error;
 use std::fmt;
 
-#[derive(Debug)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ActorError {
     ActorAlreadyExists(String),
     TargetActorNotFound(String),
@@ -11,7 +13,16 @@ pub enum ActorError {
     NotInSubscriberList(String, String),
     LockError(String),
     DividedByZero,
+    Overflow(String),
     MailboxOverflow(String),
+    StashOverflow(String),
+    Timeout(String),
+    ActorPanicked(String),
+    RestartIntensityExceeded(String),
+    CycleDetected(Vec<usize>),
+    InvalidSnapshot(String),
+    Io(String),
+    AssertionFailed(String),
 }
 
 impl fmt::Display for ActorError {
@@ -29,7 +40,21 @@ impl fmt::Display for ActorError {
             }
             ActorError::LockError(ref msg) => write!(f, "Lock error: {msg}"),
             ActorError::DividedByZero => write!(f, "Divided by zero"),
+            ActorError::Overflow(ref op) => write!(f, "Arithmetic overflow: {op}"),
             ActorError::MailboxOverflow(ref pid) => write!(f, "{pid}'s mailbox overflow"),
+            ActorError::StashOverflow(ref pid) => write!(f, "{pid}'s stash overflow"),
+            ActorError::Timeout(ref pid) => write!(f, "Timed out waiting for actor {pid}"),
+            ActorError::ActorPanicked(ref msg) => write!(f, "Actor panicked: {msg}"),
+            ActorError::RestartIntensityExceeded(ref pid) => {
+                write!(f, "Supervisor {pid} exceeded its restart intensity")
+            }
+            ActorError::CycleDetected(ref path) => {
+                let path: Vec<String> = path.iter().map(|pid| pid.to_string()).collect();
+                write!(f, "Subscription cycle detected: {}", path.join(" -> "))
+            }
+            ActorError::InvalidSnapshot(ref msg) => write!(f, "Invalid snapshot: {msg}"),
+            ActorError::Io(ref msg) => write!(f, "I/O error: {msg}"),
+            ActorError::AssertionFailed(ref msg) => write!(f, "Assertion failed: {msg}"),
         }
     }
 }
@@ -45,7 +70,22 @@ impl Error for ActorError {
             ActorError::NotInSubscriberList(_, _) => "Actor is not in the subscriber list",
             ActorError::LockError(_) => "Lock error",
             ActorError::DividedByZero => "Divided by zero",
+            ActorError::Overflow(_) => "Arithmetic overflow",
             ActorError::MailboxOverflow(_) => "Mailbox overflow",
+            ActorError::StashOverflow(_) => "Stash overflow",
+            ActorError::Timeout(_) => "Timed out",
+            ActorError::ActorPanicked(_) => "Actor panicked",
+            ActorError::RestartIntensityExceeded(_) => "Restart intensity exceeded",
+            ActorError::CycleDetected(_) => "Subscription cycle detected",
+            ActorError::InvalidSnapshot(_) => "Invalid snapshot",
+            ActorError::Io(_) => "I/O error",
+            ActorError::AssertionFailed(_) => "Assertion failed",
         }
     }
 }
+
+impl From<std::io::Error> for ActorError {
+    fn from(error: std::io::Error) -> Self {
+        ActorError::Io(error.to_string())
+    }
+}
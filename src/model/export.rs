@@ -0,0 +1,222 @@
+use std::{
+    collections::HashSet,
+    fmt::{self, Write},
+};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use super::{
+    actor::ActorPool,
+    behavior::Behavior,
+    envelope::{Envelope, Metadata},
+    errors::ActorError,
+    lifecycle::StopMode,
+    mailbox::MailboxConfig,
+    state::ActorState,
+};
+
+/// A point-in-time copy of the subscription graph of an `ActorPool`.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct GraphSnapshot<S, M> {
+    /// Every actor of the pool, ordered by id.
+    pub nodes: Vec<NodeSnapshot<S, M>>,
+    /// Every subscription, ordered by `from` then `to`.
+    pub edges: Vec<EdgeSnapshot>,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct NodeSnapshot<S, M> {
+    pub id: usize,
+    pub state: ActorState,
+    pub value: S,
+    /// Number of messages waiting in the mailbox when the snapshot was taken.
+    pub mailbox: usize,
+    /// The messages the actor has yet to handle, stashed ones first, in the order it will handle them.
+    #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
+    pub pending: Vec<M>,
+}
+
+/// A subscription: messages sent to `from` are propagated to `to`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub struct EdgeSnapshot {
+    pub from: usize,
+    pub to: usize,
+}
+
+impl<S: fmt::Debug, M> GraphSnapshot<S, M> {
+    /// Render the graph in Graphviz DOT format.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph rustor {\n");
+
+        for node in self.nodes.iter() {
+            // only the value's text is escaped, the `\n` separators are part of the DOT syntax
+            let label = format!(
+                "{}\\n{}\\nvalue: {}\\nmailbox: {}",
+                node.id,
+                node.state,
+                escape_dot(&format!("{:?}", node.value)),
+                node.mailbox
+            );
+            writeln!(dot, "    {} [label=\"{}\"];", node.id, label).unwrap();
+        }
+
+        for edge in self.edges.iter() {
+            writeln!(dot, "    {} -> {};", edge.from, edge.to).unwrap();
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+impl<B: Behavior> ActorPool<B> {
+    /// Take a snapshot of every actor and subscription of the pool.
+    pub fn snapshot(&self) -> GraphSnapshot<B::State, B::Message> {
+        let mut actors: Vec<_> = self.actor_list.lock().unwrap().values().cloned().collect();
+        actors.sort_by_key(|actor| actor.id);
+
+        let mut nodes = Vec::with_capacity(actors.len());
+        let mut edges = Vec::new();
+
+        for actor in actors {
+            let mailbox = actor.mailbox.lock().unwrap();
+            let stash = actor.stash.lock().unwrap();
+            let pending = stash
+                .iter()
+                .chain(mailbox.iter())
+                .map(|envelope| envelope.message.clone())
+                .collect();
+
+            nodes.push(NodeSnapshot {
+                id: actor.id,
+                state: *actor.state.read().unwrap(),
+                value: actor.value.read().unwrap().clone(),
+                mailbox: mailbox.len(),
+                pending,
+            });
+            drop(stash);
+            drop(mailbox);
+
+            let mut subs = actor.get_subscribers();
+            subs.sort();
+            edges.extend(
+                subs.into_iter()
+                    .map(|to| EdgeSnapshot { from: actor.id, to }),
+            );
+        }
+
+        GraphSnapshot { nodes, edges }
+    }
+
+    /// Export the subscription graph in Graphviz DOT format.
+    pub fn to_dot(&self) -> String {
+        self.snapshot().to_dot()
+    }
+
+    /// Export the subscription graph as a JSON document.
+    pub fn to_json(&self) -> Result<String, ActorError>
+    where
+        B::State: Serialize,
+        B::Message: Serialize,
+    {
+        serde_json::to_string_pretty(&self.snapshot())
+            .map_err(|err| ActorError::InvalidSnapshot(err.to_string()))
+    }
+
+    /// Rebuild a pool from a document created by `to_json`.
+    ///
+    /// Actors keep their ids, states, values and subscriptions and are driven by `B::default()`.
+    /// States are reached through the usual transitions, and the transient ones
+    /// (`Starting`, `Restarting` and `Stopping`) are rejected.
+    /// Pending messages go back to the stash of paused actors and to the mailbox of active ones,
+    /// without being propagated again; answers to `ask` are not restored.
+    pub fn from_json(json: &str) -> Result<Self, ActorError>
+    where
+        B: Default,
+        B::State: DeserializeOwned,
+        B::Message: DeserializeOwned,
+    {
+        let snapshot: GraphSnapshot<B::State, B::Message> = serde_json::from_str(json)
+            .map_err(|err| ActorError::InvalidSnapshot(err.to_string()))?;
+
+        Self::from_snapshot(snapshot)
+    }
+
+    /// Rebuild a pool from a snapshot. See `ActorPool::from_json`.
+    pub fn from_snapshot(snapshot: GraphSnapshot<B::State, B::Message>) -> Result<Self, ActorError>
+    where
+        B: Default,
+    {
+        let mut ids = HashSet::new();
+        for node in snapshot.nodes.iter() {
+            if !ids.insert(node.id) {
+                return Err(ActorError::InvalidSnapshot(format!(
+                    "actor {} appears more than once",
+                    node.id
+                )));
+            }
+            if matches!(
+                node.state,
+                ActorState::Starting | ActorState::Restarting | ActorState::Stopping
+            ) {
+                return Err(ActorError::InvalidSnapshot(format!(
+                    "actor {} is {}, which can't be restored",
+                    node.id, node.state
+                )));
+            }
+        }
+
+        for edge in snapshot.edges.iter() {
+            if !ids.contains(&edge.from) || !ids.contains(&edge.to) {
+                return Err(ActorError::InvalidSnapshot(format!(
+                    "subscription {} -> {} refers to an unknown actor",
+                    edge.from, edge.to
+                )));
+            }
+        }
+
+        let pool = ActorPool::default();
+        let mut states = Vec::with_capacity(snapshot.nodes.len());
+
+        for node in snapshot.nodes {
+            pool.ids.reserve(node.id);
+
+            let actor_ref = pool.spawn_actor(node.id, B::default(), MailboxConfig::default());
+            let actor = actor_ref.upgrade()?;
+
+            *actor.value.write().unwrap() = node.value;
+            states.push((actor, node.state, node.pending));
+        }
+
+        for edge in snapshot.edges {
+            let subscriber = pool.get_actor_info(edge.to)?;
+            pool.get_actor_info(edge.from)?.add_subscriber(subscriber)?;
+        }
+
+        // the actors are `Active` once spawned
+        for (actor, state, pending) in states {
+            match state {
+                ActorState::Paused => actor.pause()?,
+                ActorState::Stopped => {
+                    actor.stop(StopMode::Discard);
+                }
+                ActorState::Failed => {
+                    actor.transition(ActorState::Failed)?;
+                }
+                _ => {}
+            }
+
+            for message in pending {
+                // an actor that can't take its messages back records them as dead letters
+                let _ = actor.enqueue(Envelope::new(message, Metadata::new(actor.id)));
+            }
+        }
+
+        Ok(pool)
+    }
+}
+
+/// Escape `text` for a quoted DOT string: backslashes first, so that the ones escaping quotes stay as they are.
+fn escape_dot(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
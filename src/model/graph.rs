@@ -0,0 +1,296 @@
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+
+use super::errors::ActorError;
+
+/// `GraphAnalysis` describes the strongly connected components (SCCs) of a subscription graph.
+///
+/// Two actors are in the same component when each one can reach the other through subscriptions,
+/// so every cycle of the graph lies inside a single component. Collapsing each component into one
+/// node gives the condensed graph, which is always a DAG.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct GraphAnalysis {
+    /// Every component with its actor ids in ascending order. Components are listed in topological
+    /// order of the condensed graph: a publisher's component comes before its subscribers' components.
+    pub components: Vec<Vec<usize>>,
+    /// For each component, the indices of the components its actors publish to, in ascending order.
+    pub condensed: Vec<Vec<usize>>,
+    /// One concrete cycle per cyclic component, as a path that starts and ends with the same actor id
+    /// (e.g. `[1, 2, 3, 1]`, or `[4, 4]` for an actor subscribed to itself).
+    pub cycles: Vec<Vec<usize>>,
+    /// Subscriptions `(target, subscriber)` whose removal makes the graph acyclic.
+    pub feedback_edges: Vec<(usize, usize)>,
+    component_of: HashMap<usize, usize>,
+}
+
+impl GraphAnalysis {
+    /// Analyze a graph mapping every actor id to the ids of its subscribers,
+    /// such as the one returned by `ActorPool::subscription_graph`.
+    pub fn new(graph: &HashMap<usize, Vec<usize>>) -> Self {
+        let graph = normalize(graph);
+
+        let mut components = tarjan(&graph);
+        // Tarjan's algorithm completes a component only after every component reachable from it,
+        // so reversing its output gives a topological order of the condensed graph.
+        components.reverse();
+
+        let component_of: HashMap<usize, usize> = components
+            .iter()
+            .enumerate()
+            .flat_map(|(index, component)| component.iter().map(move |&id| (id, index)))
+            .collect();
+
+        let condensed = components
+            .iter()
+            .enumerate()
+            .map(|(index, component)| {
+                let targets: BTreeSet<usize> = component
+                    .iter()
+                    .flat_map(|id| graph[id].iter())
+                    .map(|sub| component_of[sub])
+                    .filter(|&target| target != index)
+                    .collect();
+
+                targets.into_iter().collect()
+            })
+            .collect();
+
+        let mut cycles = Vec::new();
+        let mut feedback_edges = Vec::new();
+
+        for component in components.iter() {
+            let members: HashSet<usize> = component.iter().copied().collect();
+            let root = component[0];
+
+            let Some(&first) = graph[&root].iter().find(|sub| members.contains(sub)) else {
+                // a single actor that isn't subscribed to itself
+                continue;
+            };
+
+            let subgraph: HashMap<usize, Vec<usize>> = component
+                .iter()
+                .map(|&id| {
+                    let subs = graph[&id]
+                        .iter()
+                        .copied()
+                        .filter(|sub| members.contains(sub))
+                        .collect();
+                    (id, subs)
+                })
+                .collect();
+
+            let mut cycle = vec![root];
+            cycle.extend(find_path(&subgraph, first, root).unwrap());
+            cycles.push(cycle);
+
+            feedback_edges.extend(back_edges(&subgraph, root));
+        }
+
+        feedback_edges.sort();
+
+        GraphAnalysis {
+            components,
+            condensed,
+            cycles,
+            feedback_edges,
+            component_of,
+        }
+    }
+
+    /// Index in `components` of the component containing `actor_id`.
+    pub fn component_of(&self, actor_id: usize) -> Option<usize> {
+        self.component_of.get(&actor_id).copied()
+    }
+
+    /// Returns `true` when the graph has no cycle.
+    pub fn is_acyclic(&self) -> bool {
+        self.cycles.is_empty()
+    }
+
+    /// Components that contain at least one cycle.
+    pub fn cyclic_components(&self) -> Vec<&Vec<usize>> {
+        self.cycles
+            .iter()
+            .map(|cycle| &self.components[self.component_of[&cycle[0]]])
+            .collect()
+    }
+
+    /// A topological order of the actors, publishers first, or `None` if the graph has a cycle.
+    pub fn topological_order(&self) -> Option<Vec<usize>> {
+        if !self.is_acyclic() {
+            return None;
+        }
+
+        Some(self.components.iter().flatten().copied().collect())
+    }
+}
+
+/// Sort every subscriber list and add the actors that only appear as subscribers.
+fn normalize(graph: &HashMap<usize, Vec<usize>>) -> HashMap<usize, Vec<usize>> {
+    let mut normalized: HashMap<usize, Vec<usize>> = HashMap::new();
+
+    for (&id, subs) in graph.iter() {
+        let mut subs = subs.clone();
+        subs.sort();
+        subs.dedup();
+
+        for &sub in subs.iter() {
+            normalized.entry(sub).or_default();
+        }
+        normalized.insert(id, subs);
+    }
+
+    normalized
+}
+
+/// Tarjan's SCC algorithm, with an explicit stack so that long subscription chains can't overflow the call stack.
+///
+/// Components are returned in reverse topological order, each sorted in ascending order.
+fn tarjan(graph: &HashMap<usize, Vec<usize>>) -> Vec<Vec<usize>> {
+    let mut nodes: Vec<usize> = graph.keys().copied().collect();
+    nodes.sort();
+
+    let mut index: HashMap<usize, usize> = HashMap::new();
+    let mut lowlink: HashMap<usize, usize> = HashMap::new();
+    let mut on_stack: HashSet<usize> = HashSet::new();
+    let mut stack: Vec<usize> = Vec::new();
+    let mut components = Vec::new();
+
+    for root in nodes {
+        if index.contains_key(&root) {
+            continue;
+        }
+
+        // each frame is a node and the position of the next subscriber to visit
+        let mut frames = vec![(root, 0)];
+        index.insert(root, index.len());
+        lowlink.insert(root, index[&root]);
+        stack.push(root);
+        on_stack.insert(root);
+
+        while let Some(&mut (node, ref mut next)) = frames.last_mut() {
+            if let Some(&sub) = graph[&node].get(*next) {
+                *next += 1;
+
+                if !index.contains_key(&sub) {
+                    index.insert(sub, index.len());
+                    lowlink.insert(sub, index[&sub]);
+                    stack.push(sub);
+                    on_stack.insert(sub);
+                    frames.push((sub, 0));
+                } else if on_stack.contains(&sub) {
+                    let low = lowlink[&node].min(index[&sub]);
+                    lowlink.insert(node, low);
+                }
+
+                continue;
+            }
+
+            frames.pop();
+
+            if let Some(&(parent, _)) = frames.last() {
+                let low = lowlink[&parent].min(lowlink[&node]);
+                lowlink.insert(parent, low);
+            }
+
+            if lowlink[&node] == index[&node] {
+                let mut component = Vec::new();
+                loop {
+                    let id = stack.pop().unwrap();
+                    on_stack.remove(&id);
+                    component.push(id);
+
+                    if id == node {
+                        break;
+                    }
+                }
+
+                component.sort();
+                components.push(component);
+            }
+        }
+    }
+
+    components
+}
+
+/// Edges pointing back to a node on the current DFS path. Removing them leaves the DFS tree
+/// and its forward and cross edges, which can't form a cycle.
+fn back_edges(graph: &HashMap<usize, Vec<usize>>, root: usize) -> Vec<(usize, usize)> {
+    let mut edges = Vec::new();
+    let mut visited = HashSet::from([root]);
+    let mut on_path = HashSet::from([root]);
+    let mut frames = vec![(root, 0)];
+
+    while let Some(&mut (node, ref mut next)) = frames.last_mut() {
+        let Some(&sub) = graph[&node].get(*next) else {
+            on_path.remove(&node);
+            frames.pop();
+            continue;
+        };
+        *next += 1;
+
+        if on_path.contains(&sub) {
+            edges.push((node, sub));
+        } else if visited.insert(sub) {
+            on_path.insert(sub);
+            frames.push((sub, 0));
+        }
+    }
+
+    edges
+}
+
+/// Add the edges `target -> subscriber` to `graph`, failing if one of them would close a cycle.
+///
+/// A new edge closes a cycle when `target` is already reachable from the subscriber,
+/// in which case the cycle is returned as `target -> subscriber -> ... -> target`.
+pub(crate) fn add_edges_acyclic(
+    graph: &mut HashMap<usize, Vec<usize>>,
+    target: usize,
+    subscribers: &[usize],
+) -> Result<(), ActorError> {
+    for &subscriber in subscribers {
+        if let Some(path) = find_path(graph, subscriber, target) {
+            let mut cycle = vec![target];
+            cycle.extend(path);
+
+            return Err(ActorError::CycleDetected(cycle));
+        }
+
+        graph.entry(target).or_default().push(subscriber);
+    }
+
+    Ok(())
+}
+
+/// Find a path from `from` to `to` in `graph` with BFS, returning the visited ids including both ends.
+pub(crate) fn find_path(
+    graph: &HashMap<usize, Vec<usize>>,
+    from: usize,
+    to: usize,
+) -> Option<Vec<usize>> {
+    let mut parents: HashMap<usize, usize> = HashMap::new();
+    let mut visited = HashSet::from([from]);
+    let mut q = VecDeque::from([from]);
+
+    while let Some(curr) = q.pop_front() {
+        if curr == to {
+            let mut path = vec![curr];
+            while let Some(&parent) = parents.get(path.last().unwrap()) {
+                path.push(parent);
+            }
+            path.reverse();
+
+            return Some(path);
+        }
+
+        for &sub in graph.get(&curr).into_iter().flatten() {
+            if visited.insert(sub) {
+                parents.insert(sub, curr);
+                q.push_back(sub);
+            }
+        }
+    }
+
+    None
+}
@@ -0,0 +1,110 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Mutex,
+};
+
+/// `IdReuse` decides whether the ids of actors removed from an `ActorPool` are handed out again.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Default)]
+pub enum IdReuse {
+    /// Every actor gets a new id: `0`, `1`, `2`, ...
+    #[default]
+    Never,
+    /// The smallest id of a removed actor is reused first, so ids stay compact.
+    LowestFirst,
+    /// The id of the actor removed first is reused first, so a released id stays unused as long as possible.
+    OldestFirst,
+}
+
+/// `IdAllocator` hands out the actor ids of a single `ActorPool`.
+///
+/// Every id has a generation, which is `0` when the id is handed out for the first time
+/// and grows every time it is reused. An id and a generation together always designate the same actor.
+#[derive(Debug, Default)]
+pub(crate) struct IdAllocator {
+    ids: Mutex<Ids>,
+}
+
+#[derive(Debug, Default)]
+struct Ids {
+    policy: IdReuse,
+    /// The smallest id that was never handed out.
+    next: usize,
+    /// Released ids, in the order they were released.
+    free: VecDeque<usize>,
+    /// The generation of every id handed out at least once.
+    generations: HashMap<usize, u64>,
+}
+
+impl IdAllocator {
+    /// Returns a new id together with its generation.
+    pub(crate) fn allocate(&self) -> (usize, u64) {
+        let mut ids = self.ids.lock().unwrap();
+
+        let reused = match ids.policy {
+            IdReuse::Never => None,
+            IdReuse::OldestFirst => ids.free.pop_front(),
+            IdReuse::LowestFirst => {
+                let lowest = ids
+                    .free
+                    .iter()
+                    .enumerate()
+                    .min_by_key(|&(_, &id)| id)
+                    .map(|(position, _)| position);
+                lowest.and_then(|position| ids.free.remove(position))
+            }
+        };
+
+        match reused {
+            Some(id) => {
+                let generation = ids.generations.entry(id).or_default();
+                *generation += 1;
+                (id, *generation)
+            }
+            None => {
+                let id = ids.next;
+                ids.next += 1;
+                ids.generations.insert(id, 0);
+                (id, 0)
+            }
+        }
+    }
+
+    /// Claim `id`, which was assigned explicitly, so that it is never handed out by `allocate` while in use.
+    pub(crate) fn reserve(&self, id: usize) -> u64 {
+        let mut ids = self.ids.lock().unwrap();
+
+        ids.free.retain(|&free| free != id);
+        if id >= ids.next {
+            ids.next = id + 1;
+        }
+
+        *ids.generations.entry(id).or_default()
+    }
+
+    /// Give back the id of a removed actor. Only reused with a policy other than `IdReuse::Never`.
+    pub(crate) fn release(&self, id: usize) {
+        let mut ids = self.ids.lock().unwrap();
+
+        if ids.policy != IdReuse::Never && !ids.free.contains(&id) {
+            ids.free.push_back(id);
+        }
+    }
+
+    pub(crate) fn generation(&self, id: usize) -> Option<u64> {
+        self.ids.lock().unwrap().generations.get(&id).copied()
+    }
+
+    pub(crate) fn policy(&self) -> IdReuse {
+        self.ids.lock().unwrap().policy
+    }
+
+    /// Switching to `IdReuse::Never` forgets the ids released so far.
+    pub(crate) fn set_policy(&self, policy: IdReuse) {
+        let mut ids = self.ids.lock().unwrap();
+
+        ids.policy = policy;
+        if policy == IdReuse::Never {
+            ids.free.clear();
+        }
+    }
+}
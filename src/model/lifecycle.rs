@@ -0,0 +1,35 @@
+use std::collections::HashMap;
+
+/// `StopMode` decides what happens to the messages still waiting in a mailbox when an actor stops.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Default)]
+pub enum StopMode {
+    /// Handle every message that is already in the mailbox before stopping.
+    #[default]
+    Drain,
+    /// Stop right after the message being handled and drop the rest of the mailbox.
+    Discard,
+}
+
+/// Returned by `ActorPool::stop_actor` and `ActorPool::remove_actor`.
+#[derive(Debug)]
+pub struct StopReport<M> {
+    pub actor_id: usize,
+    /// Messages that were left in the mailbox and never handled.
+    pub dropped: Vec<M>,
+}
+
+/// Returned by `ActorPool::shutdown`.
+#[derive(Debug)]
+pub struct ShutdownReport<M> {
+    /// Ids of every actor that was stopped and removed, in ascending order.
+    pub stopped: Vec<usize>,
+    /// Messages that were never handled, per actor. Actors without dropped messages are left out.
+    pub dropped: HashMap<usize, Vec<M>>,
+}
+
+impl<M> ShutdownReport<M> {
+    /// Total number of messages dropped over all actors.
+    pub fn dropped_count(&self) -> usize {
+        self.dropped.values().map(Vec::len).sum()
+    }
+}
@@ -0,0 +1,79 @@
+use std::{
+    io::{self, BufRead, BufReader, ErrorKind, Read, Write},
+    sync::atomic::{AtomicBool, Ordering},
+    thread,
+    time::Duration,
+};
+
+use serde::Serialize;
+
+/// How often a server checks for new connections, and whether it was shut down.
+/// Connections are read with this timeout, so that they notice the shutdown too.
+pub(crate) static POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Accept connections until `shutdown` is set, and serve each one on a thread of its own.
+///
+/// `accept` must not block: it returns `ErrorKind::WouldBlock` while no client is waiting.
+/// Returns once every connection has been served, or with the first error of `accept`.
+pub(crate) fn accept_connections<S: Send>(
+    shutdown: &AtomicBool,
+    mut accept: impl FnMut() -> io::Result<S>,
+    serve: impl Fn(S) + Sync,
+) -> io::Result<()> {
+    thread::scope(|scope| {
+        while !shutdown.load(Ordering::SeqCst) {
+            match accept() {
+                Ok(stream) => {
+                    let serve = &serve;
+                    // a client that goes away only ends its own connection
+                    scope.spawn(move || serve(stream));
+                }
+                Err(error) if error.kind() == ErrorKind::WouldBlock => thread::sleep(POLL_INTERVAL),
+                Err(error) => return Err(error),
+            }
+        }
+
+        Ok(())
+    })
+}
+
+/// Answer every line read from `reader` with the line of JSON returned by `handle`,
+/// until the client closes the connection or `shutdown` is set. Blank lines are skipped.
+///
+/// `reader` must time out after `POLL_INTERVAL`, so that `shutdown` is checked between requests.
+pub(crate) fn serve_lines<R: Serialize>(
+    reader: impl Read,
+    mut writer: impl Write,
+    shutdown: &AtomicBool,
+    mut handle: impl FnMut(&str) -> R,
+) -> io::Result<()> {
+    let mut reader = BufReader::new(reader);
+    let mut line = String::new();
+
+    while !shutdown.load(Ordering::SeqCst) {
+        // a read that times out keeps what it read so far in `line`
+        match reader.read_line(&mut line) {
+            Ok(0) => return Ok(()),
+            Ok(_) => {
+                if !line.trim().is_empty() {
+                    let response = handle(line.trim());
+                    write_line(&mut writer, &response)?;
+                }
+                line.clear();
+            }
+            Err(error) if matches!(error.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {}
+            Err(error) => return Err(error),
+        }
+    }
+
+    Ok(())
+}
+
+/// Write `value` as a single line of JSON.
+pub(crate) fn write_line(writer: &mut impl Write, value: &impl Serialize) -> io::Result<()> {
+    let mut line = serde_json::to_vec(value)?;
+    line.push(b'\n');
+
+    writer.write_all(&line)?;
+    writer.flush()
+}
@@ -0,0 +1,71 @@
+use std::time::Duration;
+
+/// The capacity of a mailbox created with `MailboxConfig::default()`.
+pub static MAILBOX_CAPACITY: usize = 10;
+
+/// The number of messages a paused actor stashes by default.
+pub static STASH_LIMIT: usize = 100;
+
+/// `OverflowPolicy` decides what happens to a message sent to a full mailbox.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Default)]
+pub enum OverflowPolicy {
+    /// Reject the new message with `ActorError::MailboxOverflow`.
+    #[default]
+    Reject,
+    /// Drop the oldest message of the mailbox to make room for the new one.
+    /// Messages sent with `ask` are kept as long as another message can be dropped;
+    /// an asked message that is dropped anyway is answered with `ActorError::MailboxOverflow`.
+    DropOldest,
+    /// Silently drop the new message.
+    DropNewest,
+    /// Block the sender until there is room in the mailbox.
+    /// With a timeout, the message is rejected with `ActorError::MailboxOverflow` once it elapses.
+    Block(Option<Duration>),
+}
+
+/// `MailboxConfig` is given to `ActorPool::spawn_with_mailbox` to configure an actor's mailbox.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct MailboxConfig {
+    /// The exact number of messages the mailbox holds, or `None` for an unbounded mailbox.
+    pub capacity: Option<usize>,
+    /// Only used by bounded mailboxes.
+    pub overflow: OverflowPolicy,
+    /// The number of messages stashed while the actor is paused, or `None` for no limit.
+    /// Messages sent to a paused actor with a full stash are rejected with `ActorError::StashOverflow`.
+    pub stash_limit: Option<usize>,
+}
+
+impl MailboxConfig {
+    pub fn bounded(capacity: usize, overflow: OverflowPolicy) -> Self {
+        MailboxConfig {
+            capacity: Some(capacity),
+            overflow,
+            stash_limit: Some(STASH_LIMIT),
+        }
+    }
+
+    pub fn unbounded() -> Self {
+        MailboxConfig {
+            capacity: None,
+            overflow: OverflowPolicy::Reject,
+            stash_limit: Some(STASH_LIMIT),
+        }
+    }
+
+    pub fn with_stash_limit(mut self, stash_limit: Option<usize>) -> Self {
+        self.stash_limit = stash_limit;
+        self
+    }
+
+    /// Returns `true` when a mailbox holding `len` messages can't take another one.
+    pub fn is_full(&self, len: usize) -> bool {
+        self.capacity.is_some_and(|capacity| len >= capacity)
+    }
+}
+
+impl Default for MailboxConfig {
+    /// A mailbox of `MAILBOX_CAPACITY` messages that rejects overflowing messages.
+    fn default() -> Self {
+        MailboxConfig::bounded(MAILBOX_CAPACITY, OverflowPolicy::Reject)
+    }
+}
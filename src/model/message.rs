@@ -1,9 +1,40 @@
-use std::fmt;
+use std::{fmt, str::FromStr};
 
-#[derive(Debug, Clone)]
+use serde::{Deserialize, Serialize};
+
+use super::errors::ActorError;
+
+/// The messages understood by the built-in `Counter` behavior.
+///
+/// Arithmetic is checked: a message that would overflow the `i32` value fails with
+/// `ActorError::Overflow`, and dividing by zero fails with `ActorError::DividedByZero`.
+/// In both cases the value is left unchanged.
+///
+/// Messages sent to remote actors are encoded in JSON, like `{"Increment": 5}`, `"Reset"`
+/// or `{"CompareAndSet": {"expected": 1, "new": 2}}`.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub enum Message {
     Increment(i32),
     Decrement(i32),
+    /// Replace the value.
+    Set(i32),
+    /// Set the value back to `INITIAL_VALUE`.
+    Reset,
+    Multiply(i32),
+    /// Integer division, rounding towards zero.
+    Divide(i32),
+    /// The remainder of the division, with the sign of the value.
+    Modulo(i32),
+    /// Cap the value: it becomes `min(value, n)`.
+    Min(i32),
+    /// Raise the value: it becomes `max(value, n)`.
+    Max(i32),
+    /// Replace the value with `new` only if it is currently `expected`.
+    /// Otherwise the message does nothing; `ask` still answers with the current value.
+    CompareAndSet {
+        expected: i32,
+        new: i32,
+    },
 }
 
 impl fmt::Display for Message {
@@ -11,6 +42,51 @@ impl fmt::Display for Message {
         match *self {
             Message::Increment(n) => write!(f, "Increment({})", n),
             Message::Decrement(n) => write!(f, "Decrement({})", n),
+            Message::Set(n) => write!(f, "Set({})", n),
+            Message::Reset => write!(f, "Reset"),
+            Message::Multiply(n) => write!(f, "Multiply({})", n),
+            Message::Divide(n) => write!(f, "Divide({})", n),
+            Message::Modulo(n) => write!(f, "Modulo({})", n),
+            Message::Min(n) => write!(f, "Min({})", n),
+            Message::Max(n) => write!(f, "Max({})", n),
+            Message::CompareAndSet { expected, new } => {
+                write!(f, "CompareAndSet({}, {})", expected, new)
+            }
         }
     }
 }
+
+/// Parses the `Display` form of a message, like `Increment(5)` or `CompareAndSet(1, 2)`,
+/// as well as the space-separated form used on the command line, like `increment 5` or `cas 1 2`.
+/// Names are case-insensitive, and `inc`, `dec`, `mul`, `div`, `mod` and `cas` are accepted as short names.
+impl FromStr for Message {
+    type Err = ActorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || ActorError::InvalidMessage(s.to_string());
+
+        let words = s.replace(['(', ')', ','], " ");
+        let mut words = words.split_whitespace();
+
+        let name = words.next().ok_or_else(invalid)?.to_ascii_lowercase();
+        let args: Vec<i32> = words
+            .map(|word| word.parse().map_err(|_| invalid()))
+            .collect::<Result<_, _>>()?;
+
+        let message = match (name.as_str(), args.as_slice()) {
+            ("increment" | "inc", &[n]) => Message::Increment(n),
+            ("decrement" | "dec", &[n]) => Message::Decrement(n),
+            ("set", &[n]) => Message::Set(n),
+            ("reset", &[]) => Message::Reset,
+            ("multiply" | "mul", &[n]) => Message::Multiply(n),
+            ("divide" | "div", &[n]) => Message::Divide(n),
+            ("modulo" | "mod", &[n]) => Message::Modulo(n),
+            ("min", &[n]) => Message::Min(n),
+            ("max", &[n]) => Message::Max(n),
+            ("compareandset" | "cas", &[expected, new]) => Message::CompareAndSet { expected, new },
+            _ => return Err(invalid()),
+        };
+
+        Ok(message)
+    }
+}
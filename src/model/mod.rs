@@ -1,4 +1,20 @@
 pub mod actor;
+pub mod actor_ref;
+pub mod dead_letter;
+pub mod behavior;
+pub mod envelope;
 pub mod errors;
+pub mod export;
+pub mod graph;
+pub mod ids;
+pub mod lifecycle;
+pub(crate) mod line;
+pub mod mailbox;
+pub mod scheduler;
+pub mod supervisor;
+pub mod timer;
 pub mod message;
+pub mod propagation;
+pub mod registry;
+pub mod remote;
 pub mod state;
@@ -0,0 +1,214 @@
+use std::{
+    collections::{hash_map::Entry, BTreeMap, HashMap, VecDeque},
+    sync::Arc,
+};
+
+use super::{
+    actor::Actor,
+    behavior::Behavior,
+    envelope::{Envelope, Metadata},
+    errors::ActorError,
+    graph::GraphAnalysis,
+};
+
+/// Every actor id mapped to the ids of its subscribers.
+type Graph = HashMap<usize, Vec<usize>>;
+
+/// `Propagation` decides how many copies of a message reach the actors downstream of its target.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Default)]
+pub enum Propagation {
+    /// Every actor receives one copy per subscription path from the target, so in a diamond
+    /// `a -> {b, c} -> d` the actor `d` receives the message twice. Fails with
+    /// `ActorError::CycleDetected` when a cycle is reachable, as the message would never stop;
+    /// the message is then refused as a whole, and recorded as a dead letter of its target.
+    #[default]
+    PerPath,
+    /// Every actor reachable from the target receives the message exactly once, cycles included.
+    ExactlyOnce,
+}
+
+/// The propagation settings of an `ActorPool`, shared with its actors.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Default)]
+pub(crate) struct Settings {
+    pub mode: Propagation,
+    /// Used by messages whose `Metadata::max_depth` is `None`.
+    pub max_depth: Option<usize>,
+}
+
+/// The copies of a message for the actors downstream of its target, planned before the target takes it.
+pub(crate) struct Plan<B: Behavior> {
+    actors: HashMap<usize, Arc<Actor<B>>>,
+    deliveries: Vec<Delivery>,
+}
+
+/// Plan the delivery of a message carrying `metadata` to every actor downstream of `origin`.
+///
+/// The reachable part of the subscription graph is computed once, so the sender's thread never
+/// recurses through the graph, and no copy goes further than `Metadata::max_depth` hops.
+/// Planning happens before `origin` takes the message, so a message that can't be propagated
+/// (see `Propagation::PerPath`) is refused without having reached any actor.
+pub(crate) fn plan<B: Behavior>(
+    origin: &Actor<B>,
+    metadata: &Metadata,
+    mode: Propagation,
+) -> Result<Plan<B>, ActorError> {
+    let remaining = metadata.remaining_hops();
+    if remaining == Some(0) {
+        return Ok(Plan::empty());
+    }
+
+    let (actors, graph) = reachable(origin);
+    if actors.is_empty() {
+        return Ok(Plan::empty());
+    }
+
+    let deliveries = match mode {
+        Propagation::ExactlyOnce => exactly_once(origin.id, &graph, remaining),
+        Propagation::PerPath => per_path(origin.id, &graph, remaining)?,
+    };
+
+    Ok(Plan { actors, deliveries })
+}
+
+impl<B: Behavior> Plan<B> {
+    fn empty() -> Self {
+        Plan {
+            actors: HashMap::new(),
+            deliveries: Vec::new(),
+        }
+    }
+
+    /// Deliver `message`, which has just been stored in `origin`'s mailbox, to the planned actors.
+    /// Every copy carries `metadata` with its hop count updated.
+    ///
+    /// `origin` already took the message, so an actor refusing its copy (e.g. with a full mailbox, or stopped)
+    /// doesn't stop the others from receiving theirs: the refused copy is recorded as a dead letter of the pool,
+    /// where it is reported with its target and the reason, and the delivery goes on.
+    pub(crate) fn deliver(self, origin: &Actor<B>, message: &B::Message, metadata: &Metadata) {
+        for Delivery {
+            actor_id,
+            distance,
+            copies,
+        } in self.deliveries
+        {
+            let actor = match self.actors.get(&actor_id) {
+                Some(actor) => actor.as_ref(),
+                // only a path that loops back to the origin gets here
+                None => origin,
+            };
+
+            for _ in 0..copies {
+                // a refused copy is recorded as a dead letter by `enqueue`
+                let _ = actor.enqueue(Envelope::new(message.clone(), metadata.forwarded(distance)));
+            }
+        }
+    }
+}
+
+/// `copies` copies of a message for `actor_id`, `distance` subscriptions away from the origin.
+struct Delivery {
+    actor_id: usize,
+    distance: usize,
+    copies: usize,
+}
+
+/// One copy for every reachable actor within `remaining` hops, in topological order of the
+/// condensed graph, so that an actor receives the message after its publishers did.
+fn exactly_once(origin: usize, graph: &Graph, remaining: Option<usize>) -> Vec<Delivery> {
+    let mut distances: HashMap<usize, usize> = HashMap::from([(origin, 0)]);
+    let mut q = VecDeque::from([origin]);
+
+    while let Some(curr) = q.pop_front() {
+        let distance = distances[&curr] + 1;
+        if remaining.is_some_and(|remaining| distance > remaining) {
+            continue;
+        }
+
+        for &sub in graph[&curr].iter() {
+            if let Entry::Vacant(entry) = distances.entry(sub) {
+                entry.insert(distance);
+                q.push_back(sub);
+            }
+        }
+    }
+
+    GraphAnalysis::new(graph)
+        .components
+        .into_iter()
+        .flatten()
+        .filter(|&id| id != origin)
+        .filter_map(|id| {
+            distances.get(&id).map(|&distance| Delivery {
+                actor_id: id,
+                distance,
+                copies: 1,
+            })
+        })
+        .collect()
+}
+
+/// One copy per path of at most `remaining` hops, level by level, so that every copy is delivered
+/// after the copy it was propagated from. Without a limit, a reachable cycle is an error.
+fn per_path(
+    origin: usize,
+    graph: &Graph,
+    remaining: Option<usize>,
+) -> Result<Vec<Delivery>, ActorError> {
+    if remaining.is_none() {
+        if let Some(cycle) = GraphAnalysis::new(graph).cycles.first() {
+            return Err(ActorError::CycleDetected(cycle.clone()));
+        }
+    }
+
+    let mut deliveries = Vec::new();
+    // number of paths from the origin to each actor of the current level
+    let mut level: BTreeMap<usize, usize> = BTreeMap::from([(origin, 1)]);
+    let mut distance = 0;
+
+    while !level.is_empty() && remaining.is_none_or(|remaining| distance < remaining) {
+        distance += 1;
+
+        let mut next: BTreeMap<usize, usize> = BTreeMap::new();
+        for (id, paths) in level {
+            for &sub in graph[&id].iter() {
+                let entry = next.entry(sub).or_default();
+                *entry = entry.saturating_add(paths);
+            }
+        }
+
+        deliveries.extend(next.iter().map(|(&actor_id, &copies)| Delivery {
+            actor_id,
+            distance,
+            copies,
+        }));
+        level = next;
+    }
+
+    Ok(deliveries)
+}
+
+/// Collect the actors reachable from `origin`, without `origin` itself,
+/// along with the subscription graph between `origin` and those actors.
+fn reachable<B: Behavior>(origin: &Actor<B>) -> (HashMap<usize, Arc<Actor<B>>>, Graph) {
+    let mut actors: HashMap<usize, Arc<Actor<B>>> = HashMap::new();
+    let mut graph: Graph = HashMap::new();
+    let mut q: VecDeque<Arc<Actor<B>>> = VecDeque::new();
+
+    let subs: Vec<Arc<Actor<B>>> = origin.subs.read().unwrap().values().cloned().collect();
+    graph.insert(origin.id, subs.iter().map(|sub| sub.id).collect());
+    q.extend(subs);
+
+    while let Some(actor) = q.pop_front() {
+        if actor.id == origin.id || actors.contains_key(&actor.id) {
+            continue;
+        }
+
+        let subs: Vec<Arc<Actor<B>>> = actor.subs.read().unwrap().values().cloned().collect();
+        graph.insert(actor.id, subs.iter().map(|sub| sub.id).collect());
+        q.extend(subs);
+
+        actors.insert(actor.id, actor);
+    }
+
+    (actors, graph)
+}
@@ -0,0 +1,132 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::RwLock,
+};
+
+use super::errors::ActorError;
+
+/// `Registry` maps the names of the actors of an `ActorPool` to their ids.
+///
+/// A name is either a plain name like `ledger`, or a hierarchical path like `/orders/shard-3`
+/// whose segments are separated by `/`. Every actor has at most one name, and every name belongs to at most one actor.
+/// The registry is shared with every actor of the pool: an actor keeps its name while it is paused or restarted,
+/// and loses it as soon as it stops or fails.
+#[derive(Debug, Default)]
+pub(crate) struct Registry {
+    entries: RwLock<Entries>,
+}
+
+#[derive(Debug, Default)]
+struct Entries {
+    /// Sorted, so that the names under a path are next to each other.
+    ids: BTreeMap<String, usize>,
+    names: HashMap<usize, String>,
+}
+
+impl Registry {
+    /// Register `actor_id` under `name`, once `is_online` confirmed that the actor can still be registered.
+    /// `is_online` is called while the registry is locked, so that a stopping actor can't be registered
+    /// after it unregistered itself.
+    pub(crate) fn register(
+        &self,
+        name: &str,
+        actor_id: usize,
+        is_online: impl FnOnce() -> bool,
+    ) -> Result<(), ActorError> {
+        validate_name(name)?;
+
+        let mut entries = self.entries.write().unwrap();
+
+        if entries.ids.get(name) == Some(&actor_id) {
+            return Ok(());
+        }
+        if entries.ids.contains_key(name) {
+            return Err(ActorError::ActorAlreadyExists(name.to_string()));
+        }
+        if let Some(current) = entries.names.get(&actor_id) {
+            return Err(ActorError::ActorAlreadyExists(format!(
+                "actor {actor_id} is already registered as {current}"
+            )));
+        }
+        if !is_online() {
+            return Err(ActorError::TargetActorIsOffline(actor_id.to_string()));
+        }
+
+        entries.ids.insert(name.to_string(), actor_id);
+        entries.names.insert(actor_id, name.to_string());
+
+        Ok(())
+    }
+
+    /// Fails with `ActorError::ActorAlreadyExists` if `name` is taken.
+    pub(crate) fn check_available(&self, name: &str) -> Result<(), ActorError> {
+        validate_name(name)?;
+
+        if self.entries.read().unwrap().ids.contains_key(name) {
+            return Err(ActorError::ActorAlreadyExists(name.to_string()));
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn lookup(&self, name: &str) -> Option<usize> {
+        self.entries.read().unwrap().ids.get(name).copied()
+    }
+
+    pub(crate) fn name_of(&self, actor_id: usize) -> Option<String> {
+        self.entries.read().unwrap().names.get(&actor_id).cloned()
+    }
+
+    /// Every name below `path`, with the id of its actor, in lexicographic order.
+    pub(crate) fn under(&self, path: &str) -> Vec<(String, usize)> {
+        let prefix = format!("{}/", path.trim_end_matches('/'));
+        let entries = self.entries.read().unwrap();
+
+        entries
+            .ids
+            .range(prefix.clone()..)
+            .take_while(|(name, _)| name.starts_with(&prefix))
+            .map(|(name, &id)| (name.clone(), id))
+            .collect()
+    }
+
+    pub(crate) fn unregister(&self, name: &str) -> Option<usize> {
+        let mut entries = self.entries.write().unwrap();
+        let actor_id = entries.ids.remove(name)?;
+        entries.names.remove(&actor_id);
+
+        Some(actor_id)
+    }
+
+    pub(crate) fn unregister_actor(&self, actor_id: usize) -> Option<String> {
+        let mut entries = self.entries.write().unwrap();
+        let name = entries.names.remove(&actor_id)?;
+        entries.ids.remove(&name);
+
+        Some(name)
+    }
+}
+
+/// A valid name is not empty and contains no whitespace. A name starting with `/` is a path
+/// made of non-empty segments; any other name can't contain `/`.
+fn validate_name(name: &str) -> Result<(), ActorError> {
+    let valid = match name.strip_prefix('/') {
+        Some(path) => path.split('/').all(is_valid_segment),
+        None => is_valid_segment(name),
+    };
+
+    if !valid {
+        return Err(ActorError::InvalidOperation(format!(
+            "{name:?} is not a valid actor name"
+        )));
+    }
+
+    Ok(())
+}
+
+fn is_valid_segment(segment: &str) -> bool {
+    !segment.is_empty()
+        && !segment
+            .chars()
+            .any(|c| c == '/' || c.is_whitespace() || c.is_control())
+}
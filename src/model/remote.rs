@@ -0,0 +1,473 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    io::{self, BufRead, BufReader, ErrorKind},
+    net::{SocketAddr, TcpListener, TcpStream},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use super::{
+    actor::{Actor, ActorPool},
+    actor_ref::ActorRef,
+    behavior::Behavior,
+    dead_letter::DeadLetterOffice,
+    envelope::Metadata,
+    errors::ActorError,
+    line,
+    state::ActorState,
+};
+
+/// How long connecting to a remote pool may take before it is considered offline,
+/// and how long a remote pool waits for an asked actor before answering `ActorError::Timeout`.
+pub static REMOTE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How long a client waits for the answer of a remote pool. Longer than `REMOTE_TIMEOUT`,
+/// so that a slow actor is reported by its pool instead of being taken for a lost connection.
+static ANSWER_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// `Link` is how an actor standing for a remote actor reaches it.
+pub(crate) trait Link<B: Behavior>: Send + Sync + fmt::Debug {
+    /// Deliver `message` to the remote actor, which propagates it to its own subscribers.
+    fn forward(&self, message: &B::Message, metadata: &Metadata) -> Result<(), ActorError>;
+
+    /// Deliver `message` and wait for the remote actor to handle it.
+    /// The outer error is a failure to reach the actor, the inner one is returned by its handler.
+    fn ask(
+        &self,
+        message: &B::Message,
+        metadata: &Metadata,
+    ) -> Result<Result<B::State, ActorError>, ActorError>;
+
+    fn value(&self) -> Result<B::State, ActorError>;
+
+    fn state(&self) -> Result<ActorState, ActorError>;
+}
+
+/// A request sent to a `RemoteServer`, as a single line of JSON.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+enum Request<M> {
+    Resolve {
+        actor_id: usize,
+    },
+    Deliver {
+        actor_id: usize,
+        message: M,
+        hops: usize,
+        max_depth: Option<usize>,
+        trace_id: u64,
+        ask: bool,
+    },
+    Value {
+        actor_id: usize,
+    },
+    State {
+        actor_id: usize,
+    },
+}
+
+/// The answer to a `Request`, sent back as `{"Ok": <Response>}` or `{"Err": <ActorError>}`.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+enum Response<S> {
+    Done,
+    Answer(Result<S, ActorError>),
+    Value(S),
+    State(ActorState),
+}
+
+impl<B: Behavior> ActorPool<B> {
+    /// Expose the actors of this pool to other pools, on `address`.
+    ///
+    /// Other pools reach them with `ActorPool::remote_actor`. Connections are neither authenticated
+    /// nor encrypted, so `address` should only be reachable by trusted hosts.
+    /// Bind to port `0` to get a free port, see `RemoteServer::local_addr`.
+    pub fn listen(&self, address: SocketAddr) -> Result<RemoteServer, ActorError>
+    where
+        B::Message: DeserializeOwned,
+        B::State: Serialize,
+    {
+        let listener = TcpListener::bind(address)?;
+        // accepting without blocking, so that the server notices when it is shut down
+        listener.set_nonblocking(true)?;
+        let address = listener.local_addr()?;
+
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let exposed = Exposed {
+            actor_list: Arc::clone(&self.actor_list),
+            dead_letters: Arc::clone(&self.dead_letters),
+            shutdown: Arc::clone(&shutdown),
+        };
+        let thread = thread::spawn(move || exposed.accept(listener));
+
+        Ok(RemoteServer {
+            address,
+            shutdown,
+            thread: Some(thread),
+        })
+    }
+
+    /// Get a reference to the actor `actor_id` of the pool listening on `address` (see `ActorPool::listen`).
+    ///
+    /// The reference points to a local actor of this pool, with its own id, that forwards every message
+    /// it receives to the remote actor. It can subscribe to local actors: their messages propagate to the
+    /// remote actor and its subscribers, and keep counting their hops. Cycles spanning several pools
+    /// are not detected, so they should be bounded with a depth limit (see `ActorPool::set_max_depth`).
+    /// Subscribing to the reference fails with `ActorError::InvalidOperation`, as the messages the remote
+    /// actor receives in its own pool never come back to this one.
+    ///
+    /// Messages don't go through the reference's mailbox: they are sent over the network by the thread
+    /// delivering them, which waits for the remote pool to take them, or to answer an `ask`. This includes
+    /// messages propagated to the reference from its publishers. Connecting may take up to `REMOTE_TIMEOUT`.
+    /// While the reference is paused, its messages are stashed and sent once it is resumed.
+    /// Timers can't target a reference (see `ActorPool::send_after`).
+    ///
+    /// Failing to reach the remote actor returns `ActorError::TargetActorIsOffline`,
+    /// and the message is recorded as a dead letter of this pool.
+    pub fn remote_actor(
+        &self,
+        address: SocketAddr,
+        actor_id: usize,
+    ) -> Result<ActorRef<B>, ActorError>
+    where
+        B: Default,
+        B::Message: Serialize,
+        B::State: DeserializeOwned,
+    {
+        let remote = self.remotes.connect(address);
+        remote.call::<B::Message, B::State>(actor_id, &Request::Resolve { actor_id })?;
+
+        let link = RemoteActor { remote, actor_id };
+
+        Ok(self.spawn_proxy(B::default(), Arc::new(link)))
+    }
+}
+
+/// `RemoteServer` is returned by `ActorPool::listen`, and serves the actors of the pool until it is
+/// shut down or dropped.
+#[derive(Debug)]
+pub struct RemoteServer {
+    address: SocketAddr,
+    shutdown: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl RemoteServer {
+    /// The address the server listens on, with the actual port if it was bound to port `0`.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// Stop accepting connections and close the open ones, after answering the requests being served.
+    pub fn shutdown(mut self) {
+        self.stop();
+    }
+
+    fn stop(&mut self) {
+        self.shutdown.store(true, Ordering::SeqCst);
+
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for RemoteServer {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// What the threads of a `RemoteServer` share with its pool.
+struct Exposed<B: Behavior> {
+    actor_list: Arc<Mutex<HashMap<usize, Arc<Actor<B>>>>>,
+    dead_letters: Arc<DeadLetterOffice<B>>,
+    shutdown: Arc<AtomicBool>,
+}
+
+impl<B: Behavior> Exposed<B>
+where
+    B::Message: DeserializeOwned,
+    B::State: Serialize,
+{
+    fn accept(self, listener: TcpListener) {
+        let accept = || listener.accept().map(|(stream, _)| stream);
+        let _ = line::accept_connections(&self.shutdown, accept, |stream| {
+            let _ = self.serve(stream);
+        });
+    }
+
+    fn serve(&self, stream: TcpStream) -> io::Result<()> {
+        stream.set_nonblocking(false)?;
+        stream.set_read_timeout(Some(line::POLL_INTERVAL))?;
+
+        line::serve_lines(stream.try_clone()?, stream, &self.shutdown, |request| {
+            match serde_json::from_str(request) {
+                Ok(request) => self.handle(request),
+                Err(error) => Err(ActorError::InvalidMessage(error.to_string())),
+            }
+        })
+    }
+
+    fn handle(&self, request: Request<B::Message>) -> Result<Response<B::State>, ActorError> {
+        match request {
+            Request::Resolve { actor_id } => self.actor(actor_id).map(|_| Response::Done),
+            Request::Deliver {
+                actor_id,
+                message,
+                hops,
+                max_depth,
+                trace_id,
+                ask,
+            } => {
+                let actor = self
+                    .actor(actor_id)
+                    .map_err(|error| self.dead_letters.reject(actor_id, message.clone(), error))?;
+                let metadata = Metadata {
+                    origin: actor_id,
+                    hops,
+                    max_depth,
+                    trace_id,
+                };
+
+                if ask {
+                    let reply = actor.ask_with_metadata(message, metadata)?;
+                    Ok(Response::Answer(reply.wait_timeout(REMOTE_TIMEOUT)))
+                } else {
+                    actor.send_with_metadata(message, metadata)?;
+                    Ok(Response::Done)
+                }
+            }
+            Request::Value { actor_id } => self.actor(actor_id)?.get_value().map(Response::Value),
+            Request::State { actor_id } => {
+                self.actor(actor_id)?.reported_state().map(Response::State)
+            }
+        }
+    }
+
+    fn actor(&self, actor_id: usize) -> Result<Arc<Actor<B>>, ActorError> {
+        self.actor_list
+            .lock()
+            .unwrap()
+            .get(&actor_id)
+            .cloned()
+            .ok_or(ActorError::TargetActorNotFound(actor_id.to_string()))
+    }
+}
+
+/// The connections of a pool to the other pools it holds remote actors of, by address.
+#[derive(Debug, Default)]
+pub(crate) struct Remotes {
+    remotes: Mutex<HashMap<SocketAddr, Arc<Remote>>>,
+}
+
+impl Remotes {
+    fn connect(&self, address: SocketAddr) -> Arc<Remote> {
+        let mut remotes = self.remotes.lock().unwrap();
+
+        Arc::clone(remotes.entry(address).or_insert_with(|| {
+            Arc::new(Remote {
+                address,
+                idle: Mutex::new(Vec::new()),
+            })
+        }))
+    }
+}
+
+/// A pool listening on `address`. Connections are opened when needed, and kept open to be reused.
+#[derive(Debug)]
+struct Remote {
+    address: SocketAddr,
+    /// Connections not used by any request.
+    idle: Mutex<Vec<Channel>>,
+}
+
+#[derive(Debug)]
+struct Channel {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+}
+
+impl Remote {
+    /// Send `request` about `actor_id` and wait for the answer.
+    ///
+    /// A kept connection closed by the remote pool is retried once with a new connection, as long as
+    /// the request can't have been handled: a request is never sent twice once the remote pool may have read it.
+    /// Failing to reach the remote pool returns `ActorError::TargetActorIsOffline`.
+    fn call<M: Serialize, S: DeserializeOwned>(
+        &self,
+        actor_id: usize,
+        request: &Request<M>,
+    ) -> Result<Response<S>, ActorError> {
+        let offline =
+            |_| ActorError::TargetActorIsOffline(format!("{actor_id} at {}", self.address));
+
+        let idle = self.idle.lock().unwrap().pop();
+        let (channel, response) = match idle {
+            Some(mut channel) => match channel.exchange(request) {
+                Ok(response) => (channel, response),
+                Err(Failure::Unsent(_)) => {
+                    let mut channel = self.open().map_err(offline)?;
+                    let response = channel
+                        .exchange(request)
+                        .map_err(|failure| offline(failure.into()))?;
+                    (channel, response)
+                }
+                Err(Failure::Unanswered(error)) => return Err(offline(error)),
+            },
+            None => {
+                let mut channel = self.open().map_err(offline)?;
+                let response = channel
+                    .exchange(request)
+                    .map_err(|failure| offline(failure.into()))?;
+                (channel, response)
+            }
+        };
+
+        let response = serde_json::from_str::<Result<Response<S>, ActorError>>(&response)
+            .map_err(|error| ActorError::InvalidMessage(error.to_string()));
+        if response.is_ok() {
+            self.idle.lock().unwrap().push(channel);
+        }
+
+        response?
+    }
+
+    fn open(&self) -> io::Result<Channel> {
+        let stream = TcpStream::connect_timeout(&self.address, REMOTE_TIMEOUT)?;
+        stream.set_nodelay(true)?;
+        stream.set_read_timeout(Some(ANSWER_TIMEOUT))?;
+        stream.set_write_timeout(Some(REMOTE_TIMEOUT))?;
+
+        Ok(Channel {
+            reader: BufReader::new(stream.try_clone()?),
+            writer: stream,
+        })
+    }
+}
+
+/// Why a `Channel` got no answer to a request.
+#[derive(Debug)]
+enum Failure {
+    /// The request didn't reach the remote pool, or the connection was closed before the pool read it:
+    /// a `RemoteServer` only closes a connection between two requests.
+    Unsent(io::Error),
+    /// The remote pool may have handled the request.
+    Unanswered(io::Error),
+}
+
+impl From<Failure> for io::Error {
+    fn from(failure: Failure) -> Self {
+        match failure {
+            Failure::Unsent(error) | Failure::Unanswered(error) => error,
+        }
+    }
+}
+
+impl Channel {
+    /// Write `request` and read the line answering it.
+    fn exchange(&mut self, request: &impl Serialize) -> Result<String, Failure> {
+        line::write_line(&mut self.writer, request).map_err(Failure::Unsent)?;
+
+        let mut response = String::new();
+        match self.reader.read_line(&mut response) {
+            Ok(0) => Err(Failure::Unsent(ErrorKind::UnexpectedEof.into())),
+            Ok(_) if !response.ends_with('\n') => {
+                Err(Failure::Unanswered(ErrorKind::UnexpectedEof.into()))
+            }
+            Ok(_) => Ok(response),
+            Err(error) => Err(Failure::Unanswered(error)),
+        }
+    }
+}
+
+/// The `Link` of an actor standing for the actor `actor_id` of `remote`.
+#[derive(Debug)]
+struct RemoteActor {
+    remote: Arc<Remote>,
+    actor_id: usize,
+}
+
+impl RemoteActor {
+    fn deliver<B: Behavior>(
+        &self,
+        message: &B::Message,
+        metadata: &Metadata,
+        ask: bool,
+    ) -> Result<Response<B::State>, ActorError>
+    where
+        B::Message: Serialize,
+        B::State: DeserializeOwned,
+    {
+        let request = Request::Deliver {
+            actor_id: self.actor_id,
+            message,
+            hops: metadata.hops,
+            max_depth: metadata.max_depth,
+            trace_id: metadata.trace_id,
+            ask,
+        };
+
+        self.remote.call(self.actor_id, &request)
+    }
+
+    fn unexpected<T>(&self) -> Result<T, ActorError> {
+        Err(ActorError::InvalidMessage(format!(
+            "unexpected answer from {}",
+            self.remote.address
+        )))
+    }
+}
+
+impl<B: Behavior> Link<B> for RemoteActor
+where
+    B::Message: Serialize,
+    B::State: DeserializeOwned,
+{
+    fn forward(&self, message: &B::Message, metadata: &Metadata) -> Result<(), ActorError> {
+        match self.deliver::<B>(message, metadata, false)? {
+            Response::Done => Ok(()),
+            _ => self.unexpected(),
+        }
+    }
+
+    fn ask(
+        &self,
+        message: &B::Message,
+        metadata: &Metadata,
+    ) -> Result<Result<B::State, ActorError>, ActorError> {
+        match self.deliver::<B>(message, metadata, true)? {
+            Response::Answer(answer) => Ok(answer),
+            _ => self.unexpected(),
+        }
+    }
+
+    fn value(&self) -> Result<B::State, ActorError> {
+        let request = Request::<B::Message>::Value {
+            actor_id: self.actor_id,
+        };
+
+        match self.remote.call(self.actor_id, &request)? {
+            Response::Value(value) => Ok(value),
+            _ => self.unexpected(),
+        }
+    }
+
+    fn state(&self) -> Result<ActorState, ActorError> {
+        let request = Request::<B::Message>::State {
+            actor_id: self.actor_id,
+        };
+
+        match self.remote.call::<_, B::State>(self.actor_id, &request)? {
+            Response::State(state) => Ok(state),
+            _ => self.unexpected(),
+        }
+    }
+}
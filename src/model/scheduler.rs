@@ -0,0 +1,101 @@
+use std::{
+    collections::VecDeque,
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+use rayon::{ThreadPool, ThreadPoolBuilder};
+
+use super::errors::ActorError;
+
+/// The number of messages an actor handles in one activation before it yields its worker.
+pub static DEFAULT_MESSAGE_BUDGET: usize = 32;
+
+type Task = Box<dyn FnOnce() + Send + 'static>;
+
+/// `Scheduler` runs actors as lightweight tasks on a fixed-size worker pool.
+///
+/// An actor is scheduled when a message arrives in its empty mailbox. Each activation handles
+/// at most `budget` messages; an actor with a non-empty mailbox is then put at the back of the
+/// run queue, so a busy actor can't starve the others.
+///
+/// A scheduler can be shared by several `ActorPool`s through `ActorPool::with_scheduler`.
+pub struct Scheduler {
+    workers: ThreadPool,
+    /// Activations waiting for a worker, in FIFO order.
+    run_queue: Arc<Mutex<VecDeque<Task>>>,
+    budget: usize,
+}
+
+impl Scheduler {
+    /// Create a scheduler with `num_workers` worker threads.
+    pub fn new(num_workers: usize, budget: usize) -> Result<Self, ActorError> {
+        if num_workers == 0 || budget == 0 {
+            return Err(ActorError::InvalidOperation(
+                "a scheduler needs at least one worker and a message budget of at least one"
+                    .to_string(),
+            ));
+        }
+
+        let workers = ThreadPoolBuilder::new()
+            .num_threads(num_workers)
+            .thread_name(|index| format!("rustor-worker-{index}"))
+            .build()
+            .map_err(|err| ActorError::InvalidOperation(err.to_string()))?;
+
+        Ok(Scheduler {
+            workers,
+            run_queue: Arc::new(Mutex::new(VecDeque::new())),
+            budget,
+        })
+    }
+
+    pub fn num_workers(&self) -> usize {
+        self.workers.current_num_threads()
+    }
+
+    /// The maximum number of messages handled per activation.
+    pub fn budget(&self) -> usize {
+        self.budget
+    }
+
+    /// Put an activation at the back of the run queue.
+    pub(crate) fn schedule<F>(&self, task: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        self.run_queue.lock().unwrap().push_back(Box::new(task));
+
+        // every queued activation is matched by exactly one job, which always runs the oldest activation.
+        // The worker pool's own queues are not FIFO across threads, so they only decide *when* something runs.
+        let run_queue = Arc::clone(&self.run_queue);
+        self.workers.spawn(move || {
+            let task = run_queue.lock().unwrap().pop_front();
+
+            if let Some(task) = task {
+                task();
+            }
+        });
+    }
+}
+
+impl Default for Scheduler {
+    /// A scheduler with one worker per available CPU and `DEFAULT_MESSAGE_BUDGET`.
+    fn default() -> Self {
+        let num_workers = std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1);
+
+        Scheduler::new(num_workers, DEFAULT_MESSAGE_BUDGET).unwrap()
+    }
+}
+
+impl fmt::Debug for Scheduler {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Scheduler")
+            .field("num_workers", &self.num_workers())
+            .field("budget", &self.budget)
+            .field("queued", &self.run_queue.lock().unwrap().len())
+            .finish()
+    }
+}
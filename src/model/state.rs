@@ -1,16 +1,69 @@
 use std::fmt;
 
-#[derive(Debug, PartialEq, Copy, Clone)]
+use serde::{Deserialize, Serialize};
+
+/// `ActorState` is the lifecycle state of an actor.
+///
+/// Actors are `Starting` until they are registered in their pool, then `Active`. From there:
+/// - `Active` and `Paused` switch to each other (`ActorPool::pause_actor` and `ActorPool::resume_actor`);
+/// - `Restarting` is entered by a restart and left for the state the actor was in before;
+/// - `Stopping` leads to `Stopped` once the actor has left its worker;
+/// - `Failed` is reached when a handler fails without a supervisor to restart the actor.
+///
+/// `Stopped` and `Failed` are final. Illegal transitions are rejected with `ActorError::InvalidOperation`.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Serialize, Deserialize)]
 pub enum ActorState {
+    /// The actor was created but isn't registered in its pool yet.
+    Starting,
+    /// The actor handles its messages.
     Active,
-    Inactive,
+    /// The actor stashes its messages instead of handling them, until it is resumed.
+    Paused,
+    /// The actor's state is being replaced by its supervisor (or `ActorPool::restart_actor`).
+    Restarting,
+    /// The actor refuses new messages and finishes the one it is handling
+    /// (or its whole mailbox with `StopMode::Drain`).
+    Stopping,
+    /// The actor is stopped for good.
+    Stopped,
+    /// The actor was brought down by a failed handler without a supervisor to restart it.
+    Failed,
+}
+
+impl ActorState {
+    /// Returns `true` if an actor in this state may move to `next`.
+    pub fn can_transition_to(self, next: ActorState) -> bool {
+        use ActorState::*;
+
+        matches!(
+            (self, next),
+            (Starting, Active | Stopping)
+                | (Active, Paused | Restarting | Stopping | Failed)
+                | (Paused, Active | Restarting | Stopping | Failed)
+                | (Restarting, Active | Paused | Stopping | Failed)
+                | (Stopping, Stopped | Failed)
+        )
+    }
+
+    /// Returns `true` once the actor doesn't accept messages anymore.
+    pub fn is_offline(self) -> bool {
+        matches!(
+            self,
+            ActorState::Stopping | ActorState::Stopped | ActorState::Failed
+        )
+    }
 }
 
 impl fmt::Display for ActorState {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
+            ActorState::Starting => write!(f, "Starting"),
             ActorState::Active => write!(f, "Active"),
-            ActorState::Inactive => write!(f, "Inactive"),
+            ActorState::Paused => write!(f, "Paused"),
+            ActorState::Restarting => write!(f, "Restarting"),
+            ActorState::Stopping => write!(f, "Stopping"),
+            ActorState::Stopped => write!(f, "Stopped"),
+            ActorState::Failed => write!(f, "Failed"),
         }
     }
 }
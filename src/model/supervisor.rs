@@ -0,0 +1,219 @@
+use std::{
+    collections::VecDeque,
+    fmt,
+    sync::{Arc, Mutex, Weak},
+    time::{Duration, Instant},
+};
+
+use super::{actor::Actor, behavior::Behavior, errors::ActorError, lifecycle::StopMode};
+
+/// Builds the state a child is restarted with.
+pub type StateFactory<S> = Arc<dyn Fn() -> S + Send + Sync>;
+
+/// `RestartStrategy` decides which children are restarted when one of them fails.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum RestartStrategy {
+    /// Only the failed child is restarted.
+    OneForOne,
+    /// Every child of the supervisor is restarted.
+    OneForAll,
+    /// The failed child and every child supervised after it are restarted.
+    RestForOne,
+}
+
+/// What a supervisor did about a failure.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum SupervisorAction {
+    /// The listed children were restarted.
+    Restarted(Vec<usize>),
+    /// The restart intensity was exceeded: every child was stopped and the failure escalated to the supervisor's parent.
+    Escalated,
+}
+
+/// A failure reported to a supervisor by one of its children.
+#[derive(Debug, Clone)]
+pub struct ChildFailure {
+    pub child_id: usize,
+    pub error: ActorError,
+    pub at: Instant,
+    pub action: SupervisorAction,
+}
+
+/// `SupervisorSpec` configures how a supervisor restarts its children.
+pub struct SupervisorSpec<S> {
+    pub strategy: RestartStrategy,
+    /// At most `max_restarts` restarts are allowed `within` the given window;
+    /// one more failure makes the supervisor give up.
+    pub max_restarts: usize,
+    pub within: Duration,
+    /// Builds the state of restarted children. Without a factory, `Behavior::initial_state` is used.
+    pub factory: Option<StateFactory<S>>,
+}
+
+impl<S> SupervisorSpec<S> {
+    /// Allows 3 restarts within 5 seconds and resets children to their initial state.
+    pub fn new(strategy: RestartStrategy) -> Self {
+        SupervisorSpec {
+            strategy,
+            max_restarts: 3,
+            within: Duration::from_secs(5),
+            factory: None,
+        }
+    }
+
+    pub fn with_intensity(mut self, max_restarts: usize, within: Duration) -> Self {
+        self.max_restarts = max_restarts;
+        self.within = within;
+        self
+    }
+
+    pub fn with_factory<F>(mut self, factory: F) -> Self
+    where
+        F: Fn() -> S + Send + Sync + 'static,
+    {
+        self.factory = Some(Arc::new(factory));
+        self
+    }
+}
+
+/// `Supervisor` watches the children of a parent actor and restarts them when they fail.
+///
+/// Children report handler errors and panics to their supervisor (see `ActorPool::supervise`).
+/// When the restart intensity is exceeded, the supervisor stops all of its children
+/// and reports the failure of the parent actor to the parent's own supervisor, forming a supervision tree.
+pub struct Supervisor<B: Behavior> {
+    pub id: usize,
+    parent: Weak<Actor<B>>,
+    spec: SupervisorSpec<B::State>,
+    children: Vec<Weak<Actor<B>>>,
+    child_ids: Vec<usize>,
+    restarts: Mutex<VecDeque<Instant>>,
+    failures: Mutex<Vec<ChildFailure>>,
+}
+
+impl<B: Behavior> Supervisor<B> {
+    pub(crate) fn new(
+        parent: &Arc<Actor<B>>,
+        children: &[Arc<Actor<B>>],
+        spec: SupervisorSpec<B::State>,
+    ) -> Self {
+        Supervisor {
+            id: parent.id,
+            parent: Arc::downgrade(parent),
+            spec,
+            children: children.iter().map(Arc::downgrade).collect(),
+            child_ids: children.iter().map(|child| child.id).collect(),
+            restarts: Mutex::new(VecDeque::new()),
+            failures: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn strategy(&self) -> RestartStrategy {
+        self.spec.strategy
+    }
+
+    /// Ids of the supervised children, in the order they were given to `ActorPool::supervise`.
+    pub fn children(&self) -> Vec<usize> {
+        self.child_ids.clone()
+    }
+
+    /// Every failure reported so far, oldest first.
+    pub fn failures(&self) -> Vec<ChildFailure> {
+        self.failures.lock().unwrap().clone()
+    }
+
+    /// Number of restarts inside the current intensity window.
+    pub fn recent_restarts(&self) -> usize {
+        let mut restarts = self.restarts.lock().unwrap();
+        Self::expire(&mut restarts, self.spec.within);
+
+        restarts.len()
+    }
+
+    /// Apply the restart strategy to a failed child.
+    pub(crate) fn child_failed(&self, child_id: usize, error: ActorError) {
+        let now = Instant::now();
+
+        let action = {
+            let mut restarts = self.restarts.lock().unwrap();
+            Self::expire(&mut restarts, self.spec.within);
+
+            if restarts.len() >= self.spec.max_restarts {
+                SupervisorAction::Escalated
+            } else {
+                restarts.push_back(now);
+                SupervisorAction::Restarted(self.restart_children(child_id))
+            }
+        };
+
+        self.failures.lock().unwrap().push(ChildFailure {
+            child_id,
+            error,
+            at: now,
+            action: action.clone(),
+        });
+
+        if action == SupervisorAction::Escalated {
+            if let Some(parent) = self.parent.upgrade() {
+                parent.fail(ActorError::RestartIntensityExceeded(self.id.to_string()));
+            }
+
+            // stop the children last, so that the failure is fully recorded once they become unreachable
+            for child in self.children.iter().filter_map(Weak::upgrade) {
+                child.stop(StopMode::Discard);
+            }
+        }
+    }
+
+    /// Restart the children selected by the strategy and return the ids of the restarted ones.
+    fn restart_children(&self, failed_id: usize) -> Vec<usize> {
+        let position = self
+            .child_ids
+            .iter()
+            .position(|&id| id == failed_id)
+            .unwrap_or(0);
+
+        let affected = match self.spec.strategy {
+            RestartStrategy::OneForOne => position..position + 1,
+            RestartStrategy::OneForAll => 0..self.children.len(),
+            RestartStrategy::RestForOne => position..self.children.len(),
+        };
+
+        // children that are already stopped or failed can't be restarted
+        self.children[affected]
+            .iter()
+            .filter_map(Weak::upgrade)
+            .filter_map(|child| {
+                let state = match self.spec.factory {
+                    Some(ref factory) => factory(),
+                    None => child.behavior.initial_state(),
+                };
+
+                child.restart(state).ok().map(|_| child.id)
+            })
+            .collect()
+    }
+
+    fn expire(restarts: &mut VecDeque<Instant>, within: Duration) {
+        let now = Instant::now();
+
+        while let Some(&oldest) = restarts.front() {
+            if now.duration_since(oldest) <= within {
+                break;
+            }
+            restarts.pop_front();
+        }
+    }
+}
+
+impl<B: Behavior> fmt::Debug for Supervisor<B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Supervisor")
+            .field("id", &self.id)
+            .field("strategy", &self.spec.strategy)
+            .field("max_restarts", &self.spec.max_restarts)
+            .field("within", &self.spec.within)
+            .field("children", &self.child_ids)
+            .finish()
+    }
+}
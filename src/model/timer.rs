@@ -0,0 +1,309 @@
+use std::{
+    fmt,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+        Arc, Condvar, Mutex,
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+/// The resolution of timers: a timer fires on the first tick after its deadline.
+pub static TIMER_TICK: Duration = Duration::from_millis(5);
+
+/// The number of slots of the timer wheel. Timers further than `WHEEL_SLOTS` ticks away
+/// go around the wheel more than once.
+pub static WHEEL_SLOTS: usize = 256;
+
+/// What a timer does when it fires. Returns `false` when it must not fire again.
+type Action = Box<dyn FnMut() -> bool + Send + 'static>;
+
+/// `TimerHandle` is returned by `ActorPool::send_after` and `ActorPool::send_interval`
+/// to follow and cancel a timer. Dropping the handle does not cancel the timer.
+#[derive(Debug, Clone)]
+pub struct TimerHandle {
+    id: u64,
+    status: Arc<Status>,
+}
+
+#[derive(Debug, Default)]
+struct Status {
+    cancelled: AtomicBool,
+    /// Set once the timer won't fire anymore without being cancelled.
+    finished: AtomicBool,
+    fired: AtomicUsize,
+}
+
+impl TimerHandle {
+    /// The id of the timer, unique within its pool.
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Prevent the timer from firing again.
+    /// Returns `false` if the timer was already cancelled or won't fire anymore.
+    pub fn cancel(&self) -> bool {
+        !self.status.finished.load(Ordering::SeqCst)
+            && !self.status.cancelled.swap(true, Ordering::SeqCst)
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.status.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Returns `true` while the timer may still fire.
+    pub fn is_pending(&self) -> bool {
+        !self.is_cancelled() && !self.status.finished.load(Ordering::SeqCst)
+    }
+
+    /// The number of times the timer has fired so far.
+    pub fn fired(&self) -> usize {
+        self.status.fired.load(Ordering::SeqCst)
+    }
+}
+
+struct Timer {
+    /// The tick on which the timer fires.
+    deadline: u64,
+    /// The number of ticks between two firings of a periodic timer.
+    period: Option<u64>,
+    status: Arc<Status>,
+    action: Action,
+}
+
+/// A hashed timer wheel: a timer is stored in the slot of its deadline tick, modulo the number of slots.
+struct Wheel {
+    slots: Vec<Vec<Timer>>,
+    /// The last tick whose slot was processed.
+    tick: u64,
+    len: usize,
+    shutdown: bool,
+}
+
+impl Wheel {
+    /// Add `timer`, `now` being the current tick.
+    fn insert(&mut self, mut timer: Timer, now: u64) {
+        // an empty wheel has no slot to process up to now, so that `advance` doesn't walk the idle ticks
+        if self.len == 0 {
+            self.tick = self.tick.max(now);
+        }
+
+        // a deadline in the past fires on the next tick
+        timer.deadline = timer.deadline.max(self.tick + 1);
+
+        let slot = (timer.deadline % WHEEL_SLOTS as u64) as usize;
+        self.slots[slot].push(timer);
+        self.len += 1;
+    }
+
+    /// Process every tick up to `now` and take the timers that are due, dropping the cancelled ones.
+    fn advance(&mut self, now: u64) -> Vec<Timer> {
+        let mut due = Vec::new();
+
+        while self.tick < now {
+            self.tick += 1;
+
+            let tick = self.tick;
+            let slot = &mut self.slots[(tick % WHEEL_SLOTS as u64) as usize];
+            let (ready, later): (Vec<Timer>, Vec<Timer>) = slot
+                .drain(..)
+                .partition(|timer| timer.deadline <= tick || is_cancelled(timer));
+            *slot = later;
+
+            self.len -= ready.len();
+            due.extend(ready.into_iter().filter(|timer| !is_cancelled(timer)));
+        }
+
+        due
+    }
+}
+
+fn is_cancelled(timer: &Timer) -> bool {
+    timer.status.cancelled.load(Ordering::SeqCst)
+}
+
+struct Shared {
+    wheel: Mutex<Wheel>,
+    /// Notified when a timer is added or the timers shut down.
+    condvar: Condvar,
+    started: Instant,
+}
+
+impl Shared {
+    /// The number of whole ticks elapsed since the wheel started.
+    fn current_tick(&self) -> u64 {
+        (self.started.elapsed().as_nanos() / TIMER_TICK.as_nanos()) as u64
+    }
+
+    /// The tick on or after which `delay` has elapsed, counted from now.
+    fn deadline(&self, delay: Duration) -> u64 {
+        let at = self.started.elapsed() + delay;
+
+        at.as_nanos().div_ceil(TIMER_TICK.as_nanos()) as u64
+    }
+
+    fn instant_of(&self, tick: u64) -> Instant {
+        self.started + Duration::from_nanos((TIMER_TICK.as_nanos() as u64).saturating_mul(tick))
+    }
+}
+
+/// `Timers` runs every timer of an `ActorPool` on a single thread, which sleeps while there is no timer.
+///
+/// Actions run on the timer thread, so they must be short: a message sent to a full mailbox with
+/// `OverflowPolicy::Block` delays every other timer until it is accepted.
+pub(crate) struct Timers {
+    shared: Arc<Shared>,
+    next_id: AtomicU64,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Timers {
+    pub(crate) fn new() -> Self {
+        let shared = Arc::new(Shared {
+            wheel: Mutex::new(Wheel {
+                slots: (0..WHEEL_SLOTS).map(|_| Vec::new()).collect(),
+                tick: 0,
+                len: 0,
+                shutdown: false,
+            }),
+            condvar: Condvar::new(),
+            started: Instant::now(),
+        });
+
+        let thread = {
+            let shared = Arc::clone(&shared);
+            thread::Builder::new()
+                .name("rustor-timer".to_string())
+                .spawn(move || run(&shared))
+                .expect("failed to spawn the timer thread")
+        };
+
+        Timers {
+            shared,
+            next_id: AtomicU64::new(0),
+            thread: Some(thread),
+        }
+    }
+
+    /// Run `action` once `delay` has elapsed, then every `period` if given,
+    /// until it is cancelled or returns `false`.
+    pub(crate) fn schedule<F>(
+        &self,
+        delay: Duration,
+        period: Option<Duration>,
+        action: F,
+    ) -> TimerHandle
+    where
+        F: FnMut() -> bool + Send + 'static,
+    {
+        let handle = TimerHandle {
+            id: self.next_id.fetch_add(1, Ordering::SeqCst),
+            status: Arc::new(Status::default()),
+        };
+
+        let timer = Timer {
+            deadline: self.shared.deadline(delay),
+            period: period.map(|period| period.as_nanos().div_ceil(TIMER_TICK.as_nanos()) as u64),
+            status: Arc::clone(&handle.status),
+            action: Box::new(action),
+        };
+
+        let now = self.shared.current_tick();
+        self.shared.wheel.lock().unwrap().insert(timer, now);
+        self.shared.condvar.notify_all();
+
+        handle
+    }
+
+    /// Cancel every pending timer.
+    pub(crate) fn cancel_all(&self) {
+        let mut wheel = self.shared.wheel.lock().unwrap();
+
+        for timer in wheel.slots.iter_mut().flat_map(|slot| slot.drain(..)) {
+            timer.status.cancelled.store(true, Ordering::SeqCst);
+        }
+        wheel.len = 0;
+    }
+
+    /// The number of timers waiting on the wheel, cancelled ones included until their deadline.
+    pub(crate) fn len(&self) -> usize {
+        self.shared.wheel.lock().unwrap().len
+    }
+}
+
+impl Drop for Timers {
+    fn drop(&mut self) {
+        self.cancel_all();
+        self.shared.wheel.lock().unwrap().shutdown = true;
+        self.shared.condvar.notify_all();
+
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl fmt::Debug for Timers {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Timers").field("len", &self.len()).finish()
+    }
+}
+
+/// The loop of the timer thread.
+fn run(shared: &Shared) {
+    let mut wheel = shared.wheel.lock().unwrap();
+
+    loop {
+        if wheel.shutdown {
+            return;
+        }
+
+        if wheel.len == 0 {
+            wheel = shared.condvar.wait(wheel).unwrap();
+            continue;
+        }
+
+        let next = shared.instant_of(wheel.tick + 1);
+        let now = Instant::now();
+        if now < next {
+            wheel = shared.condvar.wait_timeout(wheel, next - now).unwrap().0;
+            continue;
+        }
+
+        let current = shared.current_tick();
+        let due = wheel.advance(current);
+
+        // actions run without the wheel lock, so that they can add timers
+        drop(wheel);
+        let again = fire(due, current);
+
+        wheel = shared.wheel.lock().unwrap();
+        let now = shared.current_tick();
+        for timer in again {
+            wheel.insert(timer, now);
+        }
+    }
+}
+
+/// Run the actions of the due timers and return the periodic timers that must fire again.
+fn fire(due: Vec<Timer>, current: u64) -> Vec<Timer> {
+    let mut again = Vec::new();
+
+    for mut timer in due {
+        let keep = (timer.action)();
+        timer.status.fired.fetch_add(1, Ordering::SeqCst);
+
+        match timer.period {
+            Some(period) if keep && !is_cancelled(&timer) => {
+                // firings missed while the thread was busy are skipped, without drifting
+                while timer.deadline <= current {
+                    timer.deadline += period.max(1);
+                }
+                again.push(timer);
+            }
+            _ => timer.status.finished.store(true, Ordering::SeqCst),
+        }
+    }
+
+    again
+}
@@ -1,4 +1,56 @@
+mod test_actor_ref;
+mod test_behavior;
+mod test_cli;
 mod test_create;
+mod test_daemon;
+mod test_dead_letter;
+mod test_export;
+mod test_graph;
+mod test_ids;
+mod test_lifecycle;
+mod test_mailbox;
 mod test_message;
+mod test_propagation;
+mod test_registry;
+mod test_remote;
+mod test_repl;
+mod test_scenario;
+mod test_scheduler;
+mod test_stash;
 mod test_subscribe;
-mod test_update;
\ No newline at end of file
+mod test_supervisor;
+mod test_timer;
+mod test_update;
+
+#[cfg(test)]
+use std::{thread, time::Duration};
+
+#[cfg(test)]
+use crate::model::{behavior::Behavior, errors::ActorError};
+
+/// A behavior for tests that need handlers to take a while: it sleeps for the received number
+/// of milliseconds, then records it. Fails on `0`.
+#[cfg(test)]
+#[derive(Debug, Default)]
+pub(crate) struct Sleeper;
+
+#[cfg(test)]
+impl Behavior for Sleeper {
+    type State = Vec<u64>;
+    type Message = u64;
+
+    fn initial_state(&self) -> Vec<u64> {
+        Vec::new()
+    }
+
+    fn handle(&self, state: &mut Vec<u64>, message: u64) -> Result<(), ActorError> {
+        if message == 0 {
+            return Err(ActorError::InvalidMessage("0".to_string()));
+        }
+
+        thread::sleep(Duration::from_millis(message));
+        state.push(message);
+
+        Ok(())
+    }
+}
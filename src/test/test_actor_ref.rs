@@ -0,0 +1,152 @@
+#[cfg(test)]
+mod actor_ref_tests {
+    use std::thread;
+
+    use crate::model::{
+        actor::ActorPool, errors::ActorError, ids::IdReuse, lifecycle::StopMode, message::Message,
+        state::ActorState,
+    };
+
+    #[test]
+    fn test_send_and_query_through_actor_ref() {
+        let pool = ActorPool::new();
+        let actor = pool.spawn(Default::default());
+
+        assert_eq!(actor.value().unwrap(), 0);
+        assert_eq!(actor.state().unwrap(), ActorState::Active);
+
+        actor.send(Message::Increment(7)).unwrap();
+        actor.clone().send(Message::Increment(3)).unwrap();
+
+        thread::sleep(std::time::Duration::from_millis(100));
+
+        assert_eq!(actor.value().unwrap(), 10);
+        assert_eq!(pool.get_actor_value(actor.id()).unwrap(), 10);
+    }
+
+    #[test]
+    fn test_actor_ref_subscribe_propagates() {
+        let pool = ActorPool::new();
+
+        let a1 = pool.spawn(Default::default());
+        let a2 = pool.spawn(Default::default());
+        let a3 = pool.spawn(Default::default());
+
+        a1.subscribe(&[a2.clone(), a3.clone()]).unwrap();
+
+        let mut subscribers = a1.subscribers().unwrap();
+        subscribers.sort();
+        assert_eq!(subscribers, vec![a2.id(), a3.id()]);
+
+        a1.send(Message::Increment(5)).unwrap();
+
+        thread::sleep(std::time::Duration::from_millis(100));
+
+        assert_eq!(a2.value().unwrap(), 5);
+        assert_eq!(a3.value().unwrap(), 5);
+    }
+
+    #[test]
+    fn test_actor_ref_subscribe_is_atomic() {
+        let pool = ActorPool::new();
+
+        let x = pool.spawn(Default::default());
+        let y = pool.spawn(Default::default());
+        let z = pool.spawn(Default::default());
+        let removed = pool.spawn(Default::default());
+        pool.remove_actor(removed.id(), StopMode::Discard).unwrap();
+
+        x.subscribe(std::slice::from_ref(&z)).unwrap();
+
+        assert!(matches!(
+            x.subscribe(&[y.clone(), z.clone()]),
+            Err(ActorError::ActorAlreadyExists(_))
+        ));
+        assert!(matches!(
+            x.subscribe(&[y.clone(), y.clone()]),
+            Err(ActorError::ActorAlreadyExists(_))
+        ));
+        assert!(matches!(
+            x.subscribe(&[y.clone(), removed]),
+            Err(ActorError::TargetActorNotFound(_))
+        ));
+        assert_eq!(x.subscribers().unwrap(), vec![z.id()]);
+    }
+
+    #[test]
+    fn test_actor_ref_from_existing_id() {
+        let pool = ActorPool::new();
+        let id = pool.create_actor();
+
+        let actor = pool.actor_ref(id).unwrap();
+        assert_eq!(actor.id(), id);
+        assert_eq!(actor.pool_id(), pool.pool_id);
+        assert!(actor.is_alive());
+
+        let missing = pool.actor_ref(usize::MAX);
+        assert!(matches!(missing, Err(ActorError::TargetActorNotFound(_))));
+    }
+
+    #[test]
+    fn test_stale_actor_refs() {
+        let pool = ActorPool::new();
+        pool.set_id_reuse(IdReuse::LowestFirst);
+
+        // a stopped actor stays in the pool, but refuses messages
+        let stopped = pool.spawn(Default::default());
+        pool.stop_actor(stopped.id(), StopMode::Drain).unwrap();
+        assert!(stopped.is_alive());
+        assert_eq!(stopped.state().unwrap(), ActorState::Stopped);
+        assert!(matches!(
+            stopped.send(Message::Increment(1)),
+            Err(ActorError::TargetActorIsOffline(_))
+        ));
+
+        // a removed actor is gone for its references
+        let removed = pool.spawn(Default::default());
+        pool.remove_actor(removed.id(), StopMode::Drain).unwrap();
+        assert!(!removed.is_alive());
+        assert!(matches!(
+            removed.send(Message::Increment(1)),
+            Err(ActorError::TargetActorNotFound(_))
+        ));
+        assert!(matches!(
+            removed.value(),
+            Err(ActorError::TargetActorNotFound(_))
+        ));
+
+        // even once its id belongs to a new actor
+        let reused = pool.spawn(Default::default());
+        assert_eq!(reused.id(), removed.id());
+        assert_eq!(reused.generation(), removed.generation() + 1);
+        assert_ne!(reused, removed);
+        assert!(!removed.is_alive());
+        assert!(matches!(
+            removed.send(Message::Increment(1)),
+            Err(ActorError::TargetActorNotFound(_))
+        ));
+        pool.wait_idle();
+        assert_eq!(reused.value().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_actor_refs_of_different_pools_do_not_mix() {
+        let pool1 = ActorPool::new();
+        let pool2 = ActorPool::new();
+
+        let a1 = pool1.spawn(Default::default());
+        let a2 = pool2.spawn(Default::default());
+
+        assert_ne!(a1.pool_id(), a2.pool_id());
+
+        let result = a1.subscribe(std::slice::from_ref(&a2));
+        assert!(matches!(result, Err(ActorError::InvalidOperation(_))));
+        assert!(a1.subscribers().unwrap().is_empty());
+
+        assert!(pool1.resolve(&a1).is_ok());
+        assert!(matches!(
+            pool1.resolve(&a2),
+            Err(ActorError::InvalidOperation(_))
+        ));
+    }
+}
@@ -0,0 +1,94 @@
+#[cfg(test)]
+mod actor_behavior_tests {
+    use std::thread;
+
+    use crate::model::{actor::ActorPool, behavior::Behavior, errors::ActorError};
+
+    /// A behavior that records every message it receives.
+    #[derive(Debug, Default)]
+    struct Recorder;
+
+    impl Behavior for Recorder {
+        type State = Vec<String>;
+        type Message = String;
+
+        fn initial_state(&self) -> Vec<String> {
+            Vec::new()
+        }
+
+        fn handle(&self, state: &mut Vec<String>, message: String) -> Result<(), ActorError> {
+            state.push(message);
+
+            Ok(())
+        }
+    }
+
+    /// A behavior whose initial state is decided per actor.
+    #[derive(Debug)]
+    struct Offset(i64);
+
+    impl Behavior for Offset {
+        type State = i64;
+        type Message = i64;
+
+        fn initial_state(&self) -> i64 {
+            self.0
+        }
+
+        fn handle(&self, state: &mut i64, message: i64) -> Result<(), ActorError> {
+            *state += message;
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_user_defined_state_and_message() {
+        let pool = ActorPool::<Recorder>::default();
+        let id = pool.create_actor();
+
+        assert!(pool.get_actor_value(id).unwrap().is_empty());
+
+        for message in ["hello", "world"] {
+            pool.message_loop(id, message.to_string()).unwrap();
+        }
+
+        thread::sleep(std::time::Duration::from_millis(100));
+
+        assert_eq!(pool.get_actor_value(id).unwrap(), vec!["hello", "world"]);
+    }
+
+    #[test]
+    fn test_user_defined_message_propagation() {
+        let pool = ActorPool::<Recorder>::default();
+
+        let publisher = pool.create_actor();
+        let subscriber = pool.create_actor();
+
+        pool.subscribe(publisher, vec![subscriber]).unwrap();
+        pool.message_loop(publisher, "event".to_string()).unwrap();
+
+        thread::sleep(std::time::Duration::from_millis(100));
+
+        assert_eq!(pool.get_actor_value(publisher).unwrap(), vec!["event"]);
+        assert_eq!(pool.get_actor_value(subscriber).unwrap(), vec!["event"]);
+    }
+
+    #[test]
+    fn test_create_actor_with_behavior_instance() {
+        let pool = ActorPool::<Offset>::default();
+
+        let a1 = pool.create_actor_with(Offset(100));
+        let a2 = pool.create_actor_with(Offset(-5));
+
+        assert_eq!(pool.get_actor_value(a1).unwrap(), 100);
+        assert_eq!(pool.get_actor_value(a2).unwrap(), -5);
+
+        pool.message_loop(a1, 5).unwrap();
+
+        thread::sleep(std::time::Duration::from_millis(100));
+
+        assert_eq!(pool.get_actor_value(a1).unwrap(), 105);
+        assert_eq!(pool.get_actor_value(a2).unwrap(), -5);
+    }
+}
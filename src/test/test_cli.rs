@@ -0,0 +1,187 @@
+#[cfg(test)]
+mod cli_tests {
+    use std::{env, fs, process};
+
+    use clap::Parser;
+
+    use crate::{
+        cli::command::{self, Cli, Command},
+        model::{actor::ActorPool, errors::ActorError, state::ActorState},
+    };
+
+    /// Run `line` as if it was given on the command line, and return its output.
+    fn execute(pool: &ActorPool, line: &str) -> Result<String, ActorError> {
+        let cli = Cli::try_parse_from(["rustor"].into_iter().chain(line.split_whitespace()))
+            .map_err(|error| ActorError::InvalidOperation(error.to_string()))?;
+
+        let mut out = Vec::new();
+        cli.command.execute(pool, &mut out)?;
+
+        Ok(String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn test_commands() {
+        let pool = ActorPool::new();
+
+        assert_eq!(execute(&pool, "create 3").unwrap(), "0\n1\n2\n");
+        assert_eq!(execute(&pool, "subscribe 0 2 1").unwrap(), "");
+        assert_eq!(execute(&pool, "subscribers 0").unwrap(), "1\n2\n");
+
+        execute(&pool, "send 0 increment 5").unwrap();
+        execute(&pool, "send 2 dec -3").unwrap();
+        assert_eq!(execute(&pool, "value 1").unwrap(), "5\n");
+        assert_eq!(execute(&pool, "value 2").unwrap(), "8\n");
+        assert_eq!(execute(&pool, "state 2").unwrap(), "Active\n");
+
+        execute(&pool, "unsubscribe 0 2").unwrap();
+        assert_eq!(execute(&pool, "subscribers 0").unwrap(), "1\n");
+
+        assert!(matches!(
+            execute(&pool, "send 0 jump 3"),
+            Err(ActorError::InvalidMessage(_))
+        ));
+        assert!(matches!(
+            execute(&pool, "value 9"),
+            Err(ActorError::TargetActorNotFound(_))
+        ));
+        assert!(execute(&pool, "subscribe 0").is_err());
+    }
+
+    #[test]
+    fn test_cycles_and_graph() {
+        let pool = ActorPool::new();
+        execute(&pool, "create 3").unwrap();
+        execute(&pool, "subscribe 0 1").unwrap();
+
+        assert_eq!(execute(&pool, "cycles").unwrap(), "");
+        assert_eq!(execute(&pool, "detect-cycle 0").unwrap(), "false\n");
+
+        execute(&pool, "subscribe 1 0").unwrap();
+        assert_eq!(execute(&pool, "cycles").unwrap(), "0 -> 1 -> 0\n");
+        for algorithm in ["dfs", "bfs", "topo"] {
+            let line = format!("detect-cycle 0 --algorithm {algorithm}");
+            assert_eq!(execute(&pool, &line).unwrap(), "true\n");
+        }
+        assert_eq!(execute(&pool, "detect-cycle 2 -a bfs").unwrap(), "false\n");
+
+        let dot = execute(&pool, "graph").unwrap();
+        assert!(dot.starts_with("digraph rustor {"));
+        assert!(dot.contains("0 -> 1;") && dot.contains("1 -> 0;"));
+
+        let json = execute(&pool, "graph --format json").unwrap();
+        let restored: ActorPool = ActorPool::from_json(&json).unwrap();
+        assert_eq!(restored.snapshot(), pool.snapshot());
+    }
+
+    #[test]
+    fn test_run_script() {
+        let pool = ActorPool::new();
+        let script = "
+            # two counters, the second one following the first
+            create 2
+            subscribe 0 1
+
+            send 0 Increment(4)
+            send 1 mul 3
+            value 1
+        ";
+
+        let mut out = Vec::new();
+        command::run_script(script, &pool, &mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "0\n1\n12\n");
+
+        let error = command::run_script("value 0\nsend 7 inc 1", &pool, &mut Vec::new());
+        assert!(matches!(
+            error,
+            Err(ActorError::InvalidOperation(ref msg)) if msg.starts_with("line 2:")
+        ));
+
+        assert!(command::run_script("run other.txt", &pool, &mut Vec::new()).is_err());
+        assert!(command::run_script("explode 3", &pool, &mut Vec::new()).is_err());
+    }
+
+    #[test]
+    fn test_state_file() {
+        let path = env::temp_dir().join(format!("rustor-cli-{}.json", process::id()));
+        let _ = fs::remove_file(&path);
+
+        // a missing state file is an empty pool
+        let pool = command::load_pool(Some(&path)).unwrap();
+        execute(&pool, "create 2").unwrap();
+        execute(&pool, "subscribe 0 1").unwrap();
+        execute(&pool, "send 0 set 7").unwrap();
+        command::save_pool(&pool, &path).unwrap();
+
+        let pool = command::load_pool(Some(&path)).unwrap();
+        assert_eq!(execute(&pool, "value 1").unwrap(), "7\n");
+        assert_eq!(execute(&pool, "subscribers 0").unwrap(), "1\n");
+        assert_eq!(pool.get_actor_state(0).unwrap(), ActorState::Active);
+        // ids keep growing from the restored actors
+        assert_eq!(execute(&pool, "create").unwrap(), "2\n");
+
+        fs::write(&path, "not json").unwrap();
+        assert!(matches!(
+            command::load_pool(Some(&path)),
+            Err(ActorError::InvalidSnapshot(_))
+        ));
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_state_file_keeps_stashed_messages() {
+        let path = env::temp_dir().join(format!("rustor-cli-stash-{}.json", process::id()));
+        let _ = fs::remove_file(&path);
+
+        // every line runs as a separate invocation
+        for line in ["create 1", "state 0 pause", "send 0 inc 5"] {
+            let pool = command::load_pool(Some(&path)).unwrap();
+            execute(&pool, line).unwrap();
+            command::save_pool(&pool, &path).unwrap();
+        }
+
+        let pool = command::load_pool(Some(&path)).unwrap();
+        assert_eq!(pool.get_stash_len(0).unwrap(), 1);
+        execute(&pool, "state 0 resume").unwrap();
+        pool.wait_idle();
+        assert_eq!(execute(&pool, "value 0").unwrap(), "5\n");
+        assert!(pool.dead_letters().is_empty());
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_mutating_commands() {
+        let parse = |line: &str| {
+            Cli::try_parse_from(["rustor"].into_iter().chain(line.split_whitespace()))
+                .unwrap()
+                .command
+        };
+
+        for line in [
+            "create",
+            "subscribe 0 1",
+            "unsubscribe 0 1",
+            "send 0 reset",
+            "run x",
+        ] {
+            assert!(parse(line).is_mutating(), "{line:?} changes the pool");
+        }
+        for line in [
+            "state 0",
+            "value 0",
+            "subscribers 0",
+            "cycles",
+            "graph -f json",
+        ] {
+            assert!(
+                !parse(line).is_mutating(),
+                "{line:?} doesn't change the pool"
+            );
+        }
+
+        let cli = Cli::try_parse_from(["rustor", "value", "0", "--state", "pool.json"]).unwrap();
+        assert!(matches!(cli.command, Command::Value { actor_id: 0 }));
+        assert_eq!(cli.state.unwrap().to_str(), Some("pool.json"));
+    }
+}
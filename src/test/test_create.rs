@@ -4,11 +4,12 @@ mod actor_create_tests {
 
     #[test]
     fn test_create_multiple_actors_get_its_values() {
-        for i in 0..10 {
+        for _ in 0..10 {
             let actors = ActorPool::new();
             let id = actors.create_actor();
 
-            assert_eq!(id, i);
+            // every pool allocates its own ids
+            assert_eq!(id, 0);
 
             let value = actors.get_actor_value(id).unwrap();
             assert_eq!(value, 0);
@@ -0,0 +1,189 @@
+#[cfg(test)]
+mod daemon_tests {
+    use std::{
+        env, fs,
+        io::{BufRead, BufReader, Write},
+        process, thread,
+    };
+
+    #[cfg(unix)]
+    use std::os::unix::net::UnixStream;
+
+    use clap::Parser;
+
+    use crate::{
+        cli::{
+            command::{Cli, Command},
+            daemon::{self, Address, Daemon},
+        },
+        model::{actor::ActorPool, errors::ActorError},
+    };
+
+    /// Send `line` to the daemon as it would be given on the command line.
+    fn request(address: &Address, line: &str) -> Result<String, ActorError> {
+        let cli =
+            Cli::try_parse_from(["rustor"].into_iter().chain(line.split_whitespace())).unwrap();
+
+        daemon::request(address, &cli.command)
+    }
+
+    #[test]
+    fn test_serve_over_tcp() {
+        let pool = ActorPool::new();
+        let daemon = Daemon::bind(&"127.0.0.1:0".parse().unwrap()).unwrap();
+        let address = daemon.address();
+
+        thread::scope(|scope| {
+            let server = scope.spawn(|| daemon.serve(&pool));
+
+            assert_eq!(request(&address, "create 3").unwrap(), "0\n1\n2\n");
+            request(&address, "subscribe 0 1 2").unwrap();
+            request(&address, "send 0 inc 4").unwrap();
+            assert_eq!(request(&address, "value 2").unwrap(), "4\n");
+            assert_eq!(request(&address, "subscribers 0").unwrap(), "1\n2\n");
+
+            // errors keep their kind
+            assert!(matches!(
+                request(&address, "value 9"),
+                Err(ActorError::TargetActorNotFound(ref id)) if id == "9"
+            ));
+            assert!(matches!(
+                request(&address, "send 0 jump"),
+                Err(ActorError::InvalidMessage(_))
+            ));
+            assert!(matches!(
+                request(&address, "repl"),
+                Err(ActorError::InvalidOperation(_))
+            ));
+
+            request(&address, "shutdown").unwrap();
+            server.join().unwrap().unwrap();
+        });
+
+        assert_eq!(pool.get_actor_value(1).unwrap(), 4);
+
+        drop(daemon);
+        assert!(matches!(
+            request(&address, "value 0"),
+            Err(ActorError::Io(_))
+        ));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_serve_over_unix_socket() {
+        let path = env::temp_dir().join(format!("rustor-daemon-{}.sock", process::id()));
+        let address = Address::Unix(path.clone());
+        let pool = ActorPool::new();
+        pool.create_actor();
+
+        let daemon = Daemon::bind(&address).unwrap();
+        assert!(matches!(
+            Daemon::bind(&address),
+            Err(ActorError::InvalidOperation(_))
+        ));
+
+        thread::scope(|scope| {
+            let server = scope.spawn(|| daemon.serve(&pool));
+
+            // several requests over one connection, written by hand
+            let mut stream = UnixStream::connect(&path).unwrap();
+            stream
+                .write_all(
+                    b"{\"command\": \"send\", \"actor_id\": 0, \"message\": [\"Set(3)\"]}\n\n\
+                      {\"command\": \"value\", \"actor_id\": 0}\n\
+                      not json\n",
+                )
+                .unwrap();
+
+            let mut responses = BufReader::new(&stream).lines();
+            assert_eq!(responses.next().unwrap().unwrap(), r#"{"Ok":""}"#);
+            assert_eq!(responses.next().unwrap().unwrap(), r#"{"Ok":"3\n"}"#);
+            assert!(responses
+                .next()
+                .unwrap()
+                .unwrap()
+                .starts_with(r#"{"Err":{"InvalidMessage":"#));
+
+            // a second client is served while the first one is still connected
+            assert_eq!(request(&address, "value 0").unwrap(), "3\n");
+
+            request(&address, "shutdown").unwrap();
+            server.join().unwrap().unwrap();
+        });
+
+        drop(daemon);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_bind_over_regular_file() {
+        let path = env::temp_dir().join(format!("rustor-daemon-{}.json", process::id()));
+        fs::write(&path, "{}").unwrap();
+
+        assert!(matches!(
+            Daemon::bind(&Address::Unix(path.clone())),
+            Err(ActorError::InvalidOperation(_))
+        ));
+        // the file is kept as it was
+        assert_eq!(fs::read_to_string(&path).unwrap(), "{}");
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_addresses() {
+        assert_eq!(
+            "127.0.0.1:7070".parse::<Address>().unwrap(),
+            Address::Tcp("127.0.0.1:7070".parse().unwrap())
+        );
+        assert!(matches!(
+            "[::1]:7070".parse::<Address>(),
+            Ok(Address::Tcp(_))
+        ));
+        #[cfg(unix)]
+        assert_eq!(
+            "/tmp/rustor.sock".parse::<Address>().unwrap(),
+            Address::Unix("/tmp/rustor.sock".into())
+        );
+        assert!("0.0.0.0:7070".parse::<Address>().is_err());
+        assert!("".parse::<Address>().is_err());
+
+        let cli =
+            Cli::try_parse_from(["rustor", "--connect", "127.0.0.1:7070", "shutdown"]).unwrap();
+        assert!(matches!(cli.command, Command::Shutdown));
+        assert!(cli.connect.is_some());
+        assert!(
+            Cli::try_parse_from(["rustor", "--connect", "a.sock", "-s", "a.json", "cycles"])
+                .is_err()
+        );
+
+        // without a daemon, `shutdown` has nothing to do
+        assert!(cli
+            .command
+            .execute(&ActorPool::new(), &mut Vec::new())
+            .is_err());
+    }
+
+    #[test]
+    fn test_commands_as_json() {
+        let command: Command = serde_json::from_str(r#"{"command": "create"}"#).unwrap();
+        assert!(matches!(
+            command,
+            Command::Create {
+                count: 1,
+                capacity: None,
+                unbounded: false,
+                ..
+            }
+        ));
+
+        let command: Command =
+            serde_json::from_str(r#"{"command": "detect-cycle", "actor_id": 2}"#).unwrap();
+        let json = serde_json::to_string(&command).unwrap();
+        assert_eq!(
+            json,
+            r#"{"command":"detect-cycle","actor_id":2,"algorithm":"dfs"}"#
+        );
+    }
+}
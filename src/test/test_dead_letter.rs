@@ -0,0 +1,170 @@
+#[cfg(test)]
+mod dead_letter_tests {
+    use std::{thread, time::Duration};
+
+    use crate::model::{
+        actor::ActorPool,
+        dead_letter::DeadLetterReason,
+        errors::ActorError,
+        lifecycle::StopMode,
+        mailbox::{MailboxConfig, OverflowPolicy},
+        message::Message,
+    };
+    use crate::test::Sleeper;
+
+    #[test]
+    fn test_message_to_missing_actor() {
+        let pool = ActorPool::new();
+        let id = pool.create_actor();
+        let actor = pool.actor_ref(id).unwrap();
+
+        assert!(pool
+            .message_loop(usize::MAX, Message::Increment(1))
+            .is_err());
+
+        pool.remove_actor(id, StopMode::Drain).unwrap();
+        assert!(actor.send(Message::Increment(2)).is_err());
+
+        let letters = pool.dead_letters();
+        assert_eq!(letters.len(), 2);
+
+        assert_eq!(letters[0].target, usize::MAX);
+        assert_eq!(letters[0].message, Message::Increment(1));
+        assert!(matches!(letters[0].reason, DeadLetterReason::ActorNotFound));
+
+        assert_eq!(letters[1].target, id);
+        assert!(matches!(letters[1].reason, DeadLetterReason::ActorNotFound));
+        assert!(letters[0].at <= letters[1].at);
+    }
+
+    #[test]
+    fn test_rejected_and_dropped_messages() {
+        // one pool per actor, so that both actors are busy at the same time on any machine
+        let pool = ActorPool::<Sleeper>::default();
+        let other_pool = ActorPool::<Sleeper>::default();
+
+        let rejecting = pool
+            .spawn_with_mailbox(Sleeper, MailboxConfig::bounded(1, OverflowPolicy::Reject))
+            .unwrap();
+        let dropping = other_pool
+            .spawn_with_mailbox(
+                Sleeper,
+                MailboxConfig::bounded(1, OverflowPolicy::DropOldest),
+            )
+            .unwrap();
+
+        // keep both actors busy, then fill their mailboxes
+        rejecting.send(100).unwrap();
+        dropping.send(100).unwrap();
+        thread::sleep(Duration::from_millis(20));
+
+        rejecting.send(1).unwrap();
+        assert!(rejecting.send(2).is_err());
+
+        dropping.send(1).unwrap();
+        dropping.send(2).unwrap();
+
+        let rejected = pool.dead_letters_for(rejecting.id());
+        assert_eq!(rejected.len(), 1);
+        assert_eq!(rejected[0].message, 2);
+        assert!(matches!(
+            rejected[0].reason,
+            DeadLetterReason::MailboxOverflow
+        ));
+
+        let dropped = other_pool.dead_letters_for(dropping.id());
+        assert_eq!(dropped.len(), 1);
+        assert_eq!(dropped[0].message, 1);
+        assert!(matches!(dropped[0].reason, DeadLetterReason::Dropped));
+    }
+
+    #[test]
+    fn test_failed_and_discarded_messages() {
+        let pool = ActorPool::<Sleeper>::default();
+        let actor = pool.spawn(Sleeper);
+
+        // a failed request is recorded, but the actor keeps running
+        assert!(actor.ask(0).unwrap().wait().is_err());
+
+        // an unsupervised actor stops on a failure and drops its mailbox
+        actor.send(50).unwrap();
+        actor.send(0).unwrap();
+        actor.send(7).unwrap();
+        thread::sleep(Duration::from_millis(200));
+
+        let letters = pool.take_dead_letters();
+        let summary: Vec<(u64, String)> = letters
+            .iter()
+            .map(|letter| (letter.message, letter.reason.to_string()))
+            .collect();
+
+        assert_eq!(
+            summary,
+            vec![
+                (0, "handler failed: Invalid message: 0".to_string()),
+                (0, "handler failed: Invalid message: 0".to_string()),
+                (7, "discarded when the actor stopped".to_string()),
+            ]
+        );
+
+        assert!(actor.send(1).is_err());
+        assert!(matches!(
+            pool.dead_letters()[0].reason,
+            DeadLetterReason::ActorOffline
+        ));
+        assert_eq!(pool.dead_letter_count(), 4);
+    }
+
+    #[test]
+    fn test_reasons_of_refused_messages() {
+        assert!(matches!(
+            DeadLetterReason::from(ActorError::MailboxOverflow("0".to_string())),
+            DeadLetterReason::MailboxOverflow
+        ));
+        // refusals that have no reason of their own aren't taken for failed handlers
+        for error in [
+            ActorError::Io("connection reset".to_string()),
+            ActorError::InvalidOperation("0".to_string()),
+            ActorError::CycleDetected(vec![0, 1, 0]),
+        ] {
+            assert!(matches!(
+                DeadLetterReason::from(error),
+                DeadLetterReason::Other(_)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_forward_dead_letters_to_an_actor() {
+        let pool = ActorPool::<Sleeper>::default();
+
+        let failing = pool.spawn(Sleeper);
+        let sink = pool.spawn(Sleeper);
+        let downstream = pool.spawn(Sleeper);
+        sink.subscribe(std::slice::from_ref(&downstream)).unwrap();
+
+        assert!(matches!(
+            pool.set_dead_letter_actor(Some(usize::MAX)),
+            Err(ActorError::TargetActorNotFound(_))
+        ));
+        pool.set_dead_letter_actor(Some(sink.id())).unwrap();
+
+        pool.stop_actor(failing.id(), StopMode::Drain).unwrap();
+        assert!(failing.send(5).is_err());
+
+        thread::sleep(Duration::from_millis(100));
+        assert_eq!(sink.value().unwrap(), vec![5]);
+        // forwarded letters are not propagated
+        assert!(downstream.value().unwrap().is_empty());
+
+        // the sink's own dead letters are recorded, but not forwarded to itself
+        pool.unsubscribe(sink.id(), vec![downstream.id()]).unwrap();
+        sink.send(0).unwrap();
+        thread::sleep(Duration::from_millis(100));
+        assert_eq!(pool.dead_letters_for(sink.id()).len(), 1);
+
+        pool.set_dead_letter_actor(None).unwrap();
+        assert!(failing.send(6).is_err());
+        assert_eq!(pool.dead_letters_for(failing.id()).len(), 2);
+    }
+}
@@ -0,0 +1,230 @@
+#[cfg(test)]
+mod export_tests {
+    use std::{thread, time::Duration};
+
+    use crate::model::{
+        actor::ActorPool,
+        behavior::Counter,
+        errors::ActorError,
+        export::{EdgeSnapshot, GraphSnapshot, NodeSnapshot},
+        lifecycle::StopMode,
+        message::Message,
+        state::ActorState,
+    };
+
+    fn diamond() -> (ActorPool, Vec<usize>) {
+        let actors = ActorPool::new();
+        let ids: Vec<usize> = (0..4).map(|_| actors.create_actor()).collect();
+
+        actors.subscribe(ids[0], vec![ids[1], ids[2]]).unwrap();
+        actors.subscribe(ids[1], vec![ids[3]]).unwrap();
+        actors.subscribe(ids[2], vec![ids[3]]).unwrap();
+
+        (actors, ids)
+    }
+
+    #[test]
+    fn test_snapshot_lists_nodes_and_edges_in_order() {
+        let (actors, ids) = diamond();
+        actors.message_loop(ids[0], Message::Increment(2)).unwrap();
+        thread::sleep(Duration::from_millis(100));
+
+        let snapshot = actors.snapshot();
+
+        let node_ids: Vec<usize> = snapshot.nodes.iter().map(|node| node.id).collect();
+        assert_eq!(node_ids, ids);
+        assert_eq!(snapshot.nodes[3].value, 4);
+        assert_eq!(snapshot.nodes[0].state, ActorState::Active);
+
+        let edges: Vec<(usize, usize)> = snapshot
+            .edges
+            .iter()
+            .map(|edge| (edge.from, edge.to))
+            .collect();
+        assert_eq!(
+            edges,
+            vec![
+                (ids[0], ids[1]),
+                (ids[0], ids[2]),
+                (ids[1], ids[3]),
+                (ids[2], ids[3]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let (actors, ids) = diamond();
+
+        let dot = actors.to_dot();
+
+        assert!(dot.starts_with("digraph rustor {\n"));
+        assert!(dot.ends_with("}\n"));
+        assert!(dot.contains(&format!("    {} -> {};", ids[0], ids[1])));
+        assert!(dot.contains(&format!("    {} -> {};", ids[2], ids[3])));
+        assert!(dot.contains(&format!(
+            "    {} [label=\"{}\\nActive\\nvalue: 0\\nmailbox: 0\"];",
+            ids[3], ids[3]
+        )));
+    }
+
+    #[test]
+    fn test_to_dot_escapes_values() {
+        let snapshot: GraphSnapshot<String, Message> = GraphSnapshot {
+            nodes: vec![NodeSnapshot {
+                id: 0,
+                state: ActorState::Active,
+                value: r#"a"b\c"#.to_string(),
+                mailbox: 0,
+                pending: vec![],
+            }],
+            edges: vec![],
+        };
+
+        // the value's debug text is `"a\"b\\c"`, every quote and backslash of which is escaped
+        assert!(snapshot
+            .to_dot()
+            .contains(r#"    0 [label="0\nActive\nvalue: \"a\\\"b\\\\c\"\nmailbox: 0"];"#));
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let (actors, ids) = diamond();
+        actors.message_loop(ids[1], Message::Increment(5)).unwrap();
+        thread::sleep(Duration::from_millis(100));
+        actors.update_actor_state(ids[2]).unwrap();
+
+        let json = actors.to_json().unwrap();
+        let restored: ActorPool<Counter> = ActorPool::from_json(&json).unwrap();
+
+        assert_eq!(restored.snapshot(), actors.snapshot());
+        assert_eq!(restored.get_actor_value(ids[3]).unwrap(), 5);
+        assert_eq!(
+            restored.get_actor_state(ids[2]).unwrap(),
+            ActorState::Paused
+        );
+
+        // the restored pool propagates along the restored edges: ids[3] is reached through both ids[1] and ids[2]
+        restored.update_actor_state(ids[2]).unwrap();
+        restored
+            .message_loop(ids[0], Message::Increment(1))
+            .unwrap();
+        thread::sleep(Duration::from_millis(100));
+        assert_eq!(restored.get_actor_value(ids[3]).unwrap(), 7);
+
+        // ids of the restored actors are never handed out again
+        let new_id = restored.create_actor();
+        assert!(!ids.contains(&new_id));
+    }
+
+    #[test]
+    fn test_round_trip_of_every_state() {
+        let actors = ActorPool::new();
+        let ids: Vec<usize> = (0..4).map(|_| actors.create_actor()).collect();
+        actors.pause_actor(ids[1]).unwrap();
+        actors.stop_actor(ids[2], StopMode::Discard).unwrap();
+        // a failed handler without a supervisor brings its actor down
+        actors.message_loop(ids[3], Message::Divide(0)).unwrap();
+        actors.wait_idle();
+
+        let restored: ActorPool = ActorPool::from_json(&actors.to_json().unwrap()).unwrap();
+        assert_eq!(restored.snapshot(), actors.snapshot());
+
+        let states = ids.iter().map(|&id| restored.get_actor_state(id).unwrap());
+        assert_eq!(
+            states.collect::<Vec<_>>(),
+            vec![
+                ActorState::Active,
+                ActorState::Paused,
+                ActorState::Stopped,
+                ActorState::Failed
+            ]
+        );
+
+        // the restored actors behave as the states say
+        restored
+            .message_loop(ids[1], Message::Increment(1))
+            .unwrap();
+        assert_eq!(restored.get_stash_len(ids[1]).unwrap(), 1);
+
+        // stashed messages are saved along with their actor
+        let restored: ActorPool = ActorPool::from_json(&restored.to_json().unwrap()).unwrap();
+        assert_eq!(
+            restored.get_stashed_messages(ids[1]).unwrap(),
+            vec![Message::Increment(1)]
+        );
+        restored.resume_actor(ids[1]).unwrap();
+        restored.wait_idle();
+        assert_eq!(restored.get_actor_value(ids[1]).unwrap(), 1);
+        for &id in &ids[2..] {
+            assert!(matches!(
+                restored.message_loop(id, Message::Increment(1)),
+                Err(ActorError::TargetActorIsOffline(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_from_snapshot_rejects_transient_states() {
+        for state in [
+            ActorState::Starting,
+            ActorState::Restarting,
+            ActorState::Stopping,
+        ] {
+            let snapshot = GraphSnapshot {
+                nodes: vec![NodeSnapshot {
+                    id: 0,
+                    state,
+                    value: 0,
+                    mailbox: 0,
+                    pending: vec![],
+                }],
+                edges: vec![],
+            };
+
+            let result = ActorPool::<Counter>::from_snapshot(snapshot);
+            assert!(matches!(result, Err(ActorError::InvalidSnapshot(_))));
+        }
+    }
+
+    #[test]
+    fn test_from_json_rejects_malformed_documents() {
+        let result = ActorPool::<Counter>::from_json("{ not json");
+        assert!(matches!(result, Err(ActorError::InvalidSnapshot(_))));
+    }
+
+    #[test]
+    fn test_from_snapshot_rejects_duplicate_ids() {
+        let node = NodeSnapshot {
+            id: 7,
+            state: ActorState::Active,
+            value: 0,
+            mailbox: 0,
+            pending: vec![],
+        };
+        let snapshot = GraphSnapshot {
+            nodes: vec![node.clone(), node],
+            edges: vec![],
+        };
+
+        let result = ActorPool::<Counter>::from_snapshot(snapshot);
+        assert!(matches!(result, Err(ActorError::InvalidSnapshot(_))));
+    }
+
+    #[test]
+    fn test_from_snapshot_rejects_dangling_edges() {
+        let snapshot = GraphSnapshot {
+            nodes: vec![NodeSnapshot {
+                id: 7,
+                state: ActorState::Active,
+                value: 0,
+                mailbox: 0,
+                pending: vec![],
+            }],
+            edges: vec![EdgeSnapshot { from: 7, to: 8 }],
+        };
+
+        let result = ActorPool::<Counter>::from_snapshot(snapshot);
+        assert!(matches!(result, Err(ActorError::InvalidSnapshot(_))));
+    }
+}
@@ -0,0 +1,109 @@
+#[cfg(test)]
+mod graph_analysis_tests {
+    use std::collections::HashMap;
+
+    use crate::model::{actor::ActorPool, graph::GraphAnalysis};
+
+    #[test]
+    fn test_acyclic_graph() {
+        let pool = ActorPool::new();
+
+        let a1 = pool.create_actor();
+        let a2 = pool.create_actor();
+        let a3 = pool.create_actor();
+        let a4 = pool.create_actor();
+
+        pool.subscribe(a1, vec![a2, a3]).unwrap();
+        pool.subscribe(a2, vec![a4]).unwrap();
+        pool.subscribe(a3, vec![a4]).unwrap();
+
+        let analysis = pool.analyze_graph();
+
+        assert!(analysis.is_acyclic());
+        assert!(pool.find_cycles().is_empty());
+        assert!(analysis.feedback_edges.is_empty());
+        assert_eq!(analysis.components.len(), 4);
+
+        let order = analysis.topological_order().unwrap();
+        let position = |id| order.iter().position(|&x| x == id).unwrap();
+        assert!(position(a1) < position(a2));
+        assert!(position(a1) < position(a3));
+        assert!(position(a2) < position(a4));
+        assert!(position(a3) < position(a4));
+    }
+
+    #[test]
+    fn test_components_and_cycles() {
+        // 1 -> 2 -> 3 -> 1 feeds 4 <-> 5, and 6 is subscribed to itself
+        let graph = HashMap::from([
+            (1, vec![2]),
+            (2, vec![3]),
+            (3, vec![1, 4]),
+            (4, vec![5]),
+            (5, vec![4]),
+            (6, vec![6]),
+            (7, vec![]),
+        ]);
+
+        let analysis = GraphAnalysis::new(&graph);
+
+        assert!(!analysis.is_acyclic());
+        assert!(analysis.topological_order().is_none());
+        assert_eq!(analysis.components.len(), 4);
+
+        let first = analysis.component_of(1).unwrap();
+        let second = analysis.component_of(4).unwrap();
+        assert_eq!(analysis.components[first], vec![1, 2, 3]);
+        assert_eq!(analysis.components[second], vec![4, 5]);
+        assert_eq!(analysis.component_of(2), Some(first));
+        assert_eq!(analysis.component_of(8), None);
+
+        // the condensed graph keeps the single edge between the two cycles
+        assert!(first < second);
+        assert_eq!(analysis.condensed[first], vec![second]);
+        assert!(analysis.condensed[second].is_empty());
+
+        let mut cycles = analysis.cycles.clone();
+        cycles.sort();
+        assert_eq!(cycles, vec![vec![1, 2, 3, 1], vec![4, 5, 4], vec![6, 6]]);
+        assert_eq!(analysis.cyclic_components().len(), 3);
+    }
+
+    #[test]
+    fn test_removing_feedback_edges_breaks_every_cycle() {
+        let mut graph = HashMap::from([
+            (1, vec![2, 3]),
+            (2, vec![3, 1]),
+            (3, vec![1, 2, 4]),
+            (4, vec![4]),
+        ]);
+
+        let analysis = GraphAnalysis::new(&graph);
+        assert!(!analysis.feedback_edges.is_empty());
+
+        for (target, subscriber) in analysis.feedback_edges {
+            graph
+                .get_mut(&target)
+                .unwrap()
+                .retain(|&sub| sub != subscriber);
+        }
+
+        let analysis = GraphAnalysis::new(&graph);
+        assert!(analysis.is_acyclic());
+        assert_eq!(analysis.topological_order().unwrap().len(), 4);
+    }
+
+    #[test]
+    fn test_long_chain_does_not_overflow_the_stack() {
+        let n = 100_000;
+        let graph: HashMap<usize, Vec<usize>> = (0..n).map(|id| (id, vec![id + 1])).collect();
+
+        let analysis = GraphAnalysis::new(&graph);
+
+        assert!(analysis.is_acyclic());
+        assert_eq!(
+            analysis.topological_order().unwrap(),
+            (0..=n).collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,99 @@
+#[cfg(test)]
+mod id_allocation_tests {
+    use crate::model::{
+        actor::ActorPool, errors::ActorError, ids::IdReuse, lifecycle::StopMode, message::Message,
+    };
+
+    #[test]
+    fn test_pools_allocate_ids_independently() {
+        let first = ActorPool::new();
+        let second = ActorPool::new();
+
+        let first_ids: Vec<usize> = (0..3).map(|_| first.create_actor()).collect();
+        let second_ids: Vec<usize> = (0..3).map(|_| second.create_actor()).collect();
+
+        assert_eq!(first_ids, vec![0, 1, 2]);
+        assert_eq!(second_ids, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_ids_are_never_reused_by_default() {
+        let pool = ActorPool::new();
+        assert_eq!(pool.id_reuse(), IdReuse::Never);
+
+        let a = pool.create_actor();
+        pool.create_actor();
+        pool.remove_actor(a, StopMode::Drain).unwrap();
+
+        assert_eq!(pool.create_actor(), 2);
+        assert_eq!(pool.actor_generation(2).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_reuse_policies() {
+        let lowest = ActorPool::new();
+        lowest.set_id_reuse(IdReuse::LowestFirst);
+
+        let oldest = ActorPool::new();
+        oldest.set_id_reuse(IdReuse::OldestFirst);
+
+        for pool in [&lowest, &oldest] {
+            for _ in 0..4 {
+                pool.create_actor();
+            }
+            pool.remove_actor(2, StopMode::Drain).unwrap();
+            pool.remove_actor(1, StopMode::Drain).unwrap();
+        }
+
+        assert_eq!(lowest.create_actor(), 1);
+        assert_eq!(lowest.create_actor(), 2);
+        assert_eq!(lowest.create_actor(), 4);
+
+        assert_eq!(oldest.create_actor(), 2);
+        assert_eq!(oldest.create_actor(), 1);
+        assert_eq!(oldest.create_actor(), 4);
+
+        assert_eq!(lowest.actor_generation(1).unwrap(), 1);
+        assert_eq!(lowest.actor_generation(4).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_generations_detect_stale_ids() {
+        let pool = ActorPool::new();
+        pool.set_id_reuse(IdReuse::LowestFirst);
+
+        let old = pool.spawn(Default::default());
+        assert_eq!(old.generation(), 0);
+        pool.remove_actor(old.id(), StopMode::Drain).unwrap();
+
+        let new = pool.spawn(Default::default());
+        assert_eq!(new.id(), old.id());
+        assert_eq!(new.generation(), 1);
+        assert_ne!(new, old);
+
+        // the old handle never reaches the actor that reused its id
+        assert!(matches!(
+            old.send(Message::Increment(1)),
+            Err(ActorError::TargetActorNotFound(_))
+        ));
+        assert!(matches!(
+            pool.actor_ref_at(old.id(), old.generation()),
+            Err(ActorError::TargetActorNotFound(_))
+        ));
+        assert_eq!(pool.actor_ref_at(new.id(), 1).unwrap(), new);
+    }
+
+    #[test]
+    fn test_restored_ids_are_reserved() {
+        let pool = ActorPool::new();
+        pool.create_actor();
+        pool.create_actor();
+        pool.remove_actor(0, StopMode::Drain).unwrap();
+
+        let restored: ActorPool = ActorPool::from_json(&pool.to_json().unwrap()).unwrap();
+        restored.set_id_reuse(IdReuse::LowestFirst);
+
+        // id 0 is free, but not known to the restored pool as a released id
+        assert_eq!(restored.create_actor(), 2);
+    }
+}
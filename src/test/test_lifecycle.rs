@@ -0,0 +1,314 @@
+#[cfg(test)]
+mod actor_lifecycle_tests {
+    use std::{
+        sync::{Arc, Mutex},
+        thread,
+        time::Duration,
+    };
+
+    use crate::model::{
+        actor::ActorPool, behavior::Behavior, errors::ActorError, lifecycle::StopMode,
+        message::Message, state::ActorState,
+    };
+    use crate::test::Sleeper;
+
+    #[test]
+    fn test_stop_actor_rejects_new_messages() {
+        let pool = ActorPool::new();
+        let id = pool.create_actor();
+
+        pool.ask(id, Message::Increment(5)).unwrap().wait().unwrap();
+
+        let report = pool.stop_actor(id, StopMode::Drain).unwrap();
+        assert_eq!(report.actor_id, id);
+        assert!(report.dropped.is_empty());
+
+        // a stopped actor keeps its value but is offline
+        assert_eq!(pool.get_actor_value(id).unwrap(), 5);
+        assert!(pool.get_actor_info(id).unwrap().is_stopped());
+        assert!(matches!(
+            pool.message_loop(id, Message::Increment(1)),
+            Err(ActorError::TargetActorIsOffline(_))
+        ));
+
+        // stopping twice is harmless
+        assert!(pool.stop_actor(id, StopMode::Drain).is_ok());
+    }
+
+    #[test]
+    fn test_stop_actor_drains_mailbox() {
+        let pool = ActorPool::<Sleeper>::default();
+        let id = pool.create_actor();
+
+        for _ in 0..5 {
+            pool.message_loop(id, 10).unwrap();
+        }
+
+        let report = pool.stop_actor(id, StopMode::Drain).unwrap();
+
+        assert!(report.dropped.is_empty());
+        assert_eq!(pool.get_actor_value(id).unwrap(), vec![10; 5]);
+    }
+
+    #[test]
+    fn test_stop_actor_discards_mailbox() {
+        let pool = ActorPool::<Sleeper>::default();
+        let id = pool.create_actor();
+
+        pool.message_loop(id, 100).unwrap();
+        thread::sleep(Duration::from_millis(20));
+
+        for n in 1..=3 {
+            pool.message_loop(id, n).unwrap();
+        }
+
+        let report = pool.stop_actor(id, StopMode::Discard).unwrap();
+
+        // only the message being handled while stopping was processed
+        assert_eq!(report.dropped, vec![1, 2, 3]);
+        assert_eq!(pool.get_actor_value(id).unwrap(), vec![100]);
+    }
+
+    #[test]
+    fn test_discarded_ask_is_answered_as_offline() {
+        let pool = ActorPool::<Sleeper>::default();
+        let id = pool.create_actor();
+
+        pool.message_loop(id, 100).unwrap();
+        thread::sleep(Duration::from_millis(20));
+
+        let reply = pool.ask(id, 1).unwrap();
+        pool.stop_actor(id, StopMode::Discard).unwrap();
+
+        assert!(matches!(
+            reply.wait(),
+            Err(ActorError::TargetActorIsOffline(_))
+        ));
+    }
+
+    #[test]
+    fn test_remove_actor_unhooks_subscriptions() {
+        let pool = ActorPool::new();
+
+        let a1 = pool.create_actor();
+        let a2 = pool.create_actor();
+        let a3 = pool.create_actor();
+
+        pool.subscribe(a1, vec![a2, a3]).unwrap();
+        pool.subscribe(a2, vec![a3]).unwrap();
+
+        let a2_ref = pool.actor_ref(a2).unwrap();
+
+        pool.remove_actor(a2, StopMode::Drain).unwrap();
+
+        assert!(matches!(
+            pool.get_actor_info(a2),
+            Err(ActorError::TargetActorNotFound(_))
+        ));
+        assert_eq!(pool.get_actor_subscribers(a1).unwrap(), vec![a3]);
+
+        // the handle notices that the actor is gone
+        assert!(!a2_ref.is_alive());
+        assert!(matches!(
+            a2_ref.send(Message::Increment(1)),
+            Err(ActorError::TargetActorNotFound(_))
+        ));
+
+        // propagation keeps working for the remaining actors
+        pool.ask(a1, Message::Increment(2)).unwrap().wait().unwrap();
+        pool.ask(a3, Message::Increment(0)).unwrap().wait().unwrap();
+        assert_eq!(pool.get_actor_value(a3).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_stop_actor_unhooks_it_from_its_publishers() {
+        let pool = ActorPool::new();
+
+        let publisher = pool.create_actor();
+        let stopped = pool.create_actor();
+        let other = pool.create_actor();
+        pool.subscribe(publisher, vec![stopped, other]).unwrap();
+        pool.subscribe(stopped, vec![other]).unwrap();
+
+        pool.stop_actor(stopped, StopMode::Drain).unwrap();
+        assert_eq!(pool.get_actor_subscribers(publisher).unwrap(), vec![other]);
+        // its own subscribers can still be read
+        assert_eq!(pool.get_actor_subscribers(stopped).unwrap(), vec![other]);
+
+        // sending to the publisher neither fails nor leaves dead letters for the stopped actor
+        pool.message_loop(publisher, Message::Increment(3)).unwrap();
+        pool.wait_idle();
+        assert_eq!(pool.get_actor_value(other).unwrap(), 3);
+        assert!(pool.dead_letters().is_empty());
+    }
+
+    #[test]
+    fn test_shutdown_reports_dropped_messages() {
+        let pool = ActorPool::<Sleeper>::default();
+
+        let busy = pool.create_actor();
+        let idle = pool.create_actor();
+
+        pool.message_loop(busy, 100).unwrap();
+        thread::sleep(Duration::from_millis(20));
+        pool.message_loop(busy, 1).unwrap();
+        pool.message_loop(busy, 2).unwrap();
+
+        let report = pool.shutdown(StopMode::Discard);
+
+        let mut expected = vec![busy, idle];
+        expected.sort();
+
+        assert_eq!(report.stopped, expected);
+        assert_eq!(report.dropped.get(&busy), Some(&vec![1, 2]));
+        assert!(!report.dropped.contains_key(&idle));
+        assert_eq!(report.dropped_count(), 2);
+        assert!(pool.actor_list.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_shutdown_breaks_subscription_cycles() {
+        let pool = ActorPool::new();
+
+        let a1 = pool.spawn(Default::default());
+        let a2 = pool.spawn(Default::default());
+
+        a1.subscribe(std::slice::from_ref(&a2)).unwrap();
+        a2.subscribe(std::slice::from_ref(&a1)).unwrap();
+
+        let report = pool.shutdown(StopMode::Drain);
+
+        assert_eq!(report.stopped.len(), 2);
+        assert!(!a1.is_alive());
+        assert!(!a2.is_alive());
+    }
+
+    /// `(hook, from, to)`
+    type HookCall = (&'static str, ActorState, ActorState);
+
+    /// Records every hook call.
+    #[derive(Debug, Default)]
+    struct Hooked {
+        calls: Arc<Mutex<Vec<HookCall>>>,
+    }
+
+    impl Behavior for Hooked {
+        type State = u64;
+        type Message = u64;
+
+        fn initial_state(&self) -> u64 {
+            0
+        }
+
+        fn handle(&self, state: &mut u64, message: u64) -> Result<(), ActorError> {
+            *state += message;
+
+            Ok(())
+        }
+
+        fn pre_transition(&self, _: usize, from: ActorState, to: ActorState) {
+            self.calls.lock().unwrap().push(("pre", from, to));
+        }
+
+        fn post_transition(&self, _: usize, from: ActorState, to: ActorState) {
+            self.calls.lock().unwrap().push(("post", from, to));
+        }
+    }
+
+    #[test]
+    fn test_transition_hooks() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let pool = ActorPool::<Hooked>::default();
+        let actor = pool.spawn(Hooked {
+            calls: Arc::clone(&calls),
+        });
+
+        pool.pause_actor(actor.id()).unwrap();
+        pool.resume_actor(actor.id()).unwrap();
+        pool.stop_actor(actor.id(), StopMode::Drain).unwrap();
+
+        use ActorState::*;
+        let transitions = [
+            (Starting, Active),
+            (Active, Paused),
+            (Paused, Active),
+            (Active, Stopping),
+            (Stopping, Stopped),
+        ];
+        let expected: Vec<_> = transitions
+            .iter()
+            .flat_map(|&(from, to)| [("pre", from, to), ("post", from, to)])
+            .collect();
+
+        assert_eq!(*calls.lock().unwrap(), expected);
+    }
+
+    #[test]
+    fn test_illegal_transitions_are_rejected() {
+        let pool = ActorPool::new();
+        let id = pool.create_actor();
+
+        assert_eq!(pool.get_actor_state(id).unwrap(), ActorState::Active);
+        assert!(matches!(
+            pool.resume_actor(id),
+            Err(ActorError::InvalidOperation(_))
+        ));
+
+        pool.pause_actor(id).unwrap();
+        assert!(matches!(
+            pool.pause_actor(id),
+            Err(ActorError::InvalidOperation(_))
+        ));
+
+        pool.stop_actor(id, StopMode::Drain).unwrap();
+        assert_eq!(pool.get_actor_state(id).unwrap(), ActorState::Stopped);
+
+        assert!(matches!(
+            pool.update_actor_state(id),
+            Err(ActorError::InvalidOperation(_))
+        ));
+        assert!(matches!(
+            pool.restart_actor(id),
+            Err(ActorError::InvalidOperation(_))
+        ));
+        assert_eq!(pool.get_actor_state(id).unwrap(), ActorState::Stopped);
+
+        assert!(ActorState::Stopping.can_transition_to(ActorState::Stopped));
+        assert!(!ActorState::Stopped.can_transition_to(ActorState::Active));
+        assert!(!ActorState::Failed.can_transition_to(ActorState::Restarting));
+    }
+
+    #[test]
+    fn test_restart_actor() {
+        let pool = ActorPool::new();
+        let id = pool.create_actor();
+
+        pool.ask(id, Message::Increment(5)).unwrap().wait().unwrap();
+        pool.pause_actor(id).unwrap();
+        pool.restart_actor(id).unwrap();
+
+        // the actor is back in the state it was restarted from, with its initial value
+        assert_eq!(pool.get_actor_state(id).unwrap(), ActorState::Paused);
+        assert_eq!(pool.get_actor_value(id).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_failed_actor_is_offline() {
+        let pool = ActorPool::new();
+        let id = pool.create_actor();
+
+        pool.message_loop(id, Message::Divide(0)).unwrap();
+        thread::sleep(Duration::from_millis(100));
+
+        assert_eq!(pool.get_actor_state(id).unwrap(), ActorState::Failed);
+        assert!(matches!(
+            pool.message_loop(id, Message::Increment(1)),
+            Err(ActorError::TargetActorIsOffline(_))
+        ));
+        assert!(matches!(
+            pool.stop_actor(id, StopMode::Drain),
+            Ok(ref report) if report.dropped.is_empty()
+        ));
+        assert_eq!(pool.get_actor_state(id).unwrap(), ActorState::Failed);
+    }
+}
@@ -0,0 +1,149 @@
+#[cfg(test)]
+mod mailbox_tests {
+    use std::{thread, time::Duration};
+
+    use crate::model::{
+        actor::ActorPool,
+        actor_ref::ActorRef,
+        errors::ActorError,
+        mailbox::{MailboxConfig, OverflowPolicy},
+    };
+    use crate::test::Sleeper;
+
+    /// Spawn an actor with the given mailbox and keep it busy for 100ms.
+    fn busy_actor(pool: &ActorPool<Sleeper>, mailbox: MailboxConfig) -> ActorRef<Sleeper> {
+        let actor = pool.spawn_with_mailbox(Sleeper, mailbox).unwrap();
+
+        actor.send(100).unwrap();
+        thread::sleep(Duration::from_millis(20));
+
+        actor
+    }
+
+    #[test]
+    fn test_reject_overflowing_message() {
+        let pool = ActorPool::<Sleeper>::default();
+        let actor = busy_actor(&pool, MailboxConfig::bounded(2, OverflowPolicy::Reject));
+
+        actor.send(1).unwrap();
+        actor.send(2).unwrap();
+
+        assert!(matches!(actor.send(3), Err(ActorError::MailboxOverflow(_))));
+        assert_eq!(pool.get_mailbox_len(actor.id()).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_default_mailbox_holds_exactly_ten_messages() {
+        let pool = ActorPool::<Sleeper>::default();
+        let actor = busy_actor(&pool, MailboxConfig::default());
+
+        for _ in 0..10 {
+            actor.send(1).unwrap();
+        }
+
+        assert!(matches!(actor.send(1), Err(ActorError::MailboxOverflow(_))));
+    }
+
+    #[test]
+    fn test_drop_oldest_message() {
+        let pool = ActorPool::<Sleeper>::default();
+        let actor = busy_actor(&pool, MailboxConfig::bounded(2, OverflowPolicy::DropOldest));
+
+        for n in 1..=3 {
+            actor.send(n).unwrap();
+        }
+
+        assert_eq!(pool.get_mailbox_len(actor.id()).unwrap(), 2);
+
+        // asking makes room by dropping `2` as well
+        let reply = actor.ask(4).unwrap();
+        assert_eq!(reply.wait().unwrap(), vec![100, 3, 4]);
+    }
+
+    #[test]
+    fn test_drop_oldest_keeps_asked_messages() {
+        let pool = ActorPool::<Sleeper>::default();
+        let actor = busy_actor(&pool, MailboxConfig::bounded(2, OverflowPolicy::DropOldest));
+
+        let asked = actor.ask(1).unwrap();
+        actor.send(2).unwrap();
+        // `2` is dropped rather than the older `ask`
+        actor.send(3).unwrap();
+        assert_eq!(asked.wait().unwrap(), vec![100, 1]);
+
+        let pool = ActorPool::<Sleeper>::default();
+        let actor = busy_actor(&pool, MailboxConfig::bounded(1, OverflowPolicy::DropOldest));
+
+        // with nothing else to drop, the asking side is told why it gets no answer
+        let asked = actor.ask(1).unwrap();
+        actor.send(2).unwrap();
+        assert!(matches!(asked.wait(), Err(ActorError::MailboxOverflow(_))));
+        pool.wait_idle();
+        assert_eq!(actor.value().unwrap(), vec![100, 2]);
+    }
+
+    #[test]
+    fn test_drop_newest_message() {
+        let pool = ActorPool::<Sleeper>::default();
+        let actor = busy_actor(&pool, MailboxConfig::bounded(2, OverflowPolicy::DropNewest));
+
+        for n in 1..=3 {
+            actor.send(n).unwrap();
+        }
+
+        thread::sleep(Duration::from_millis(150));
+
+        assert_eq!(actor.value().unwrap(), vec![100, 1, 2]);
+    }
+
+    #[test]
+    fn test_block_sender_until_there_is_room() {
+        let pool = ActorPool::<Sleeper>::default();
+        let actor = busy_actor(
+            &pool,
+            MailboxConfig::bounded(1, OverflowPolicy::Block(None)),
+        );
+
+        for n in 1..=3 {
+            actor.send(n).unwrap();
+        }
+
+        let reply = actor.ask(4).unwrap();
+        assert_eq!(reply.wait().unwrap(), vec![100, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_block_sender_with_timeout() {
+        let pool = ActorPool::<Sleeper>::default();
+        let actor = busy_actor(
+            &pool,
+            MailboxConfig::bounded(1, OverflowPolicy::Block(Some(Duration::from_millis(10)))),
+        );
+
+        actor.send(1).unwrap();
+
+        assert!(matches!(actor.send(2), Err(ActorError::MailboxOverflow(_))));
+    }
+
+    #[test]
+    fn test_unbounded_mailbox() {
+        let pool = ActorPool::<Sleeper>::default();
+        let actor = busy_actor(&pool, MailboxConfig::unbounded());
+
+        for _ in 0..1000 {
+            actor.send(1).unwrap();
+        }
+
+        assert_eq!(pool.get_mailbox_len(actor.id()).unwrap(), 1000);
+    }
+
+    #[test]
+    fn test_zero_capacity_is_rejected() {
+        let pool = ActorPool::<Sleeper>::default();
+
+        assert!(matches!(
+            pool.spawn_with_mailbox(Sleeper, MailboxConfig::bounded(0, OverflowPolicy::Reject)),
+            Err(ActorError::InvalidOperation(_))
+        ));
+    }
+}
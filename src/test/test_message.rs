@@ -1,8 +1,10 @@
 #[cfg(test)]
 mod message_handling_test {
-    use std::thread;
+    use std::{thread, time::Duration};
 
     use crate::model::actor::ActorPool;
+    use crate::model::behavior::Behavior;
+    use crate::model::errors::ActorError;
     use crate::model::message::Message;
     use crate::model::state::ActorState;
 
@@ -78,7 +80,7 @@ mod message_handling_test {
 
         let actor_state = pool.update_actor_state(actor).unwrap();
 
-        assert_eq!(actor_state, ActorState::Inactive);
+        assert_eq!(actor_state, ActorState::Paused);
 
         // Send message to inactive actor
         let messages = vec![
@@ -100,6 +102,259 @@ mod message_handling_test {
         assert_eq!(actor_state, ActorState::Active);
 
         // Read messages from the actor's mailbox and update the actor's value
+        pool.wait_idle();
         assert_eq!(pool.get_actor_value(actor).unwrap(), 60);
     }
+
+    #[test]
+    fn test_ask_returns_value_after_handling() {
+        let pool = ActorPool::new();
+        let id = pool.create_actor();
+
+        for value in [10, 20, 55] {
+            pool.message_loop(id, Message::Increment(value)).unwrap();
+        }
+
+        // messages are handled in order, so the answer already includes the previous ones
+        let reply = pool.ask(id, Message::Increment(130)).unwrap();
+        assert_eq!(reply.actor_id(), id);
+        assert_eq!(reply.wait().unwrap(), 215);
+
+        let reply = pool.ask(id, Message::Decrement(15)).unwrap();
+        assert_eq!(reply.wait_timeout(Duration::from_secs(1)).unwrap(), 200);
+    }
+
+    #[test]
+    fn test_ask_propagates_to_subscribers() {
+        let pool = ActorPool::new();
+
+        let actor1 = pool.create_actor();
+        let actor2 = pool.create_actor();
+
+        pool.subscribe(actor1, vec![actor2]).unwrap();
+
+        let reply = pool.ask(actor1, Message::Increment(3)).unwrap();
+        assert_eq!(reply.wait().unwrap(), 3);
+
+        let reply = pool.ask(actor2, Message::Increment(0)).unwrap();
+        assert_eq!(reply.wait().unwrap(), 3);
+    }
+
+    #[test]
+    fn test_ask_unknown_actor() {
+        let pool = ActorPool::new();
+
+        let result = pool.ask(usize::MAX, Message::Increment(1));
+        assert!(matches!(result, Err(ActorError::TargetActorNotFound(_))));
+    }
+
+    #[test]
+    fn test_ask_reports_handler_error() {
+        let pool = ActorPool::<Fallible>::default();
+        let id = pool.create_actor();
+
+        let reply = pool.ask(id, 0).unwrap();
+        assert!(matches!(
+            reply.wait_timeout(Duration::from_secs(1)),
+            Err(ActorError::DividedByZero)
+        ));
+
+        // the actor keeps working after a failed request
+        let reply = pool.ask(id, 5).unwrap();
+        assert_eq!(reply.wait().unwrap(), 20);
+    }
+
+    #[test]
+    fn test_ask_times_out() {
+        let pool = ActorPool::<Fallible>::default();
+        let id = pool.create_actor();
+
+        let reply = pool.ask(id, -1).unwrap();
+        assert!(matches!(
+            reply.wait_timeout(Duration::from_millis(50)),
+            Err(ActorError::Timeout(_))
+        ));
+    }
+
+    #[test]
+    fn test_arithmetic_messages() {
+        let pool = ActorPool::new();
+        let id = pool.create_actor();
+
+        let ask = |message| pool.ask(id, message).unwrap().wait().unwrap();
+
+        assert_eq!(ask(Message::Set(7)), 7);
+        assert_eq!(ask(Message::Multiply(-6)), -42);
+        assert_eq!(ask(Message::Divide(4)), -10);
+        assert_eq!(ask(Message::Modulo(3)), -1);
+        assert_eq!(ask(Message::Set(50)), 50);
+        assert_eq!(ask(Message::Min(20)), 20);
+        assert_eq!(ask(Message::Max(25)), 25);
+        assert_eq!(ask(Message::Max(5)), 25);
+        assert_eq!(ask(Message::Reset), 0);
+    }
+
+    #[test]
+    fn test_compare_and_set() {
+        let pool = ActorPool::new();
+        let id = pool.create_actor();
+
+        let reply = pool
+            .ask(
+                id,
+                Message::CompareAndSet {
+                    expected: 0,
+                    new: 5,
+                },
+            )
+            .unwrap();
+        assert_eq!(reply.wait().unwrap(), 5);
+
+        // the value isn't the expected one anymore, so nothing changes
+        let reply = pool
+            .ask(
+                id,
+                Message::CompareAndSet {
+                    expected: 0,
+                    new: 9,
+                },
+            )
+            .unwrap();
+        assert_eq!(reply.wait().unwrap(), 5);
+    }
+
+    #[test]
+    fn test_checked_arithmetic() {
+        let pool = ActorPool::new();
+        let id = pool.create_actor();
+
+        pool.ask(id, Message::Set(i32::MAX))
+            .unwrap()
+            .wait()
+            .unwrap();
+
+        for message in [Message::Increment(1), Message::Multiply(2)] {
+            let result = pool.ask(id, message).unwrap().wait();
+            assert!(matches!(result, Err(ActorError::Overflow(_))));
+        }
+
+        pool.ask(id, Message::Set(i32::MIN))
+            .unwrap()
+            .wait()
+            .unwrap();
+
+        for message in [
+            Message::Decrement(1),
+            Message::Divide(-1),
+            Message::Modulo(-1),
+        ] {
+            let result = pool.ask(id, message).unwrap().wait();
+            assert!(matches!(result, Err(ActorError::Overflow(_))));
+        }
+
+        for message in [Message::Divide(0), Message::Modulo(0)] {
+            let result = pool.ask(id, message).unwrap().wait();
+            assert!(matches!(result, Err(ActorError::DividedByZero)));
+        }
+
+        // failed messages leave the value untouched
+        assert_eq!(pool.get_actor_value(id).unwrap(), i32::MIN);
+    }
+
+    #[test]
+    fn test_overflow_stops_unsupervised_actor() {
+        let pool = ActorPool::new();
+        let id = pool.create_actor();
+
+        pool.message_loop(id, Message::Set(i32::MAX)).unwrap();
+        pool.message_loop(id, Message::Increment(1)).unwrap();
+        thread::sleep(Duration::from_millis(100));
+
+        assert_eq!(pool.get_actor_value(id).unwrap(), i32::MAX);
+        assert!(matches!(
+            pool.message_loop(id, Message::Reset),
+            Err(ActorError::TargetActorIsOffline(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_messages() {
+        let parsed: Vec<Message> = [
+            "Increment(5)",
+            "dec -3",
+            "SET 7",
+            "reset",
+            "mul 2",
+            "Divide(4)",
+            "mod 3",
+            "min -1",
+            "Max(10)",
+            "CompareAndSet(1, 2)",
+            "cas 1 2",
+        ]
+        .iter()
+        .map(|text| text.parse().unwrap())
+        .collect();
+
+        assert_eq!(
+            parsed,
+            vec![
+                Message::Increment(5),
+                Message::Decrement(-3),
+                Message::Set(7),
+                Message::Reset,
+                Message::Multiply(2),
+                Message::Divide(4),
+                Message::Modulo(3),
+                Message::Min(-1),
+                Message::Max(10),
+                Message::CompareAndSet {
+                    expected: 1,
+                    new: 2
+                },
+                Message::CompareAndSet {
+                    expected: 1,
+                    new: 2
+                },
+            ]
+        );
+
+        // the `Display` form parses back to the same message
+        for message in parsed {
+            assert_eq!(message.to_string().parse::<Message>().unwrap(), message);
+        }
+
+        for text in ["", "jump 3", "increment", "increment x", "reset 1", "cas 1"] {
+            assert!(
+                matches!(text.parse::<Message>(), Err(ActorError::InvalidMessage(_))),
+                "{text:?} should be rejected"
+            );
+        }
+    }
+
+    /// Divides 100 by the received number; sleeps for a while on negative numbers.
+    #[derive(Debug, Default)]
+    struct Fallible;
+
+    impl Behavior for Fallible {
+        type State = i32;
+        type Message = i32;
+
+        fn initial_state(&self) -> i32 {
+            0
+        }
+
+        fn handle(&self, state: &mut i32, message: i32) -> Result<(), ActorError> {
+            if message < 0 {
+                thread::sleep(Duration::from_millis(200));
+                return Ok(());
+            }
+
+            *state = 100_i32
+                .checked_div(message)
+                .ok_or(ActorError::DividedByZero)?;
+
+            Ok(())
+        }
+    }
 }
@@ -0,0 +1,320 @@
+#[cfg(test)]
+mod propagation_tests {
+    use std::{
+        thread,
+        time::{Duration, Instant},
+    };
+
+    use crate::model::{
+        actor::ActorPool,
+        behavior::{Behavior, Counter},
+        dead_letter::DeadLetterReason,
+        envelope::Metadata,
+        errors::ActorError,
+        mailbox::MailboxConfig,
+        message::Message,
+        propagation::Propagation,
+    };
+
+    /// a -> {b, c} -> d
+    fn diamond(pool: &ActorPool) -> (usize, usize, usize, usize) {
+        let a = pool.create_actor();
+        let b = pool.create_actor();
+        let c = pool.create_actor();
+        let d = pool.create_actor();
+
+        pool.subscribe(a, vec![b, c]).unwrap();
+        pool.subscribe(b, vec![d]).unwrap();
+        pool.subscribe(c, vec![d]).unwrap();
+
+        (a, b, c, d)
+    }
+
+    fn wait_for_value(pool: &ActorPool, actor_id: usize, expected: i32) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+
+        while pool.get_actor_value(actor_id).unwrap() != expected && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(10));
+        }
+
+        assert_eq!(pool.get_actor_value(actor_id).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_per_path_is_the_default() {
+        let pool = ActorPool::new();
+        let (a, b, c, d) = diamond(&pool);
+
+        assert_eq!(pool.propagation(), Propagation::PerPath);
+        pool.message_loop(a, Message::Increment(1)).unwrap();
+
+        wait_for_value(&pool, d, 2);
+        assert_eq!(pool.get_actor_value(a).unwrap(), 1);
+        assert_eq!(pool.get_actor_value(b).unwrap(), 1);
+        assert_eq!(pool.get_actor_value(c).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_exactly_once() {
+        let pool = ActorPool::new();
+        let (a, b, c, d) = diamond(&pool);
+
+        pool.set_propagation(Propagation::ExactlyOnce);
+        pool.message_loop(a, Message::Increment(1)).unwrap();
+
+        wait_for_value(&pool, a, 1);
+        wait_for_value(&pool, b, 1);
+        wait_for_value(&pool, c, 1);
+        thread::sleep(Duration::from_millis(100));
+        assert_eq!(pool.get_actor_value(d).unwrap(), 1);
+
+        // actor handles follow the pool's propagation too
+        pool.actor_ref(a)
+            .unwrap()
+            .send(Message::Increment(1))
+            .unwrap();
+
+        wait_for_value(&pool, a, 2);
+        thread::sleep(Duration::from_millis(100));
+        assert_eq!(pool.get_actor_value(d).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_propagation_can_be_chosen_per_message() {
+        let pool = ActorPool::new();
+        let (a, _, _, d) = diamond(&pool);
+
+        pool.message_loop_with(a, Message::Increment(1), Propagation::ExactlyOnce)
+            .unwrap();
+        wait_for_value(&pool, d, 1);
+
+        pool.message_loop_with(a, Message::Increment(1), Propagation::PerPath)
+            .unwrap();
+        wait_for_value(&pool, d, 3);
+    }
+
+    #[test]
+    fn test_per_path_rejects_reachable_cycles() {
+        let pool = ActorPool::new();
+
+        let a = pool.create_actor();
+        let b = pool.create_actor();
+        let c = pool.create_actor();
+
+        pool.subscribe(a, vec![b]).unwrap();
+        pool.subscribe(b, vec![c]).unwrap();
+        pool.subscribe(c, vec![b]).unwrap();
+
+        let result = pool.message_loop(a, Message::Increment(1));
+        assert!(
+            matches!(result, Err(ActorError::CycleDetected(ref cycle)) if *cycle == vec![b, c, b])
+        );
+        assert!(matches!(
+            pool.ask(a, Message::Increment(2)),
+            Err(ActorError::CycleDetected(_))
+        ));
+
+        // the message is refused as a whole: not even the target gets it
+        thread::sleep(Duration::from_millis(100));
+        for id in [a, b, c] {
+            assert_eq!(pool.get_actor_value(id).unwrap(), 0);
+        }
+
+        let letters = pool.dead_letters();
+        assert_eq!(letters.len(), 2);
+        assert!(letters.iter().all(|letter| letter.target == a
+            && matches!(
+                letter.reason,
+                DeadLetterReason::Other(ActorError::CycleDetected(_))
+            )));
+        assert_eq!(letters[0].message, Message::Increment(1));
+    }
+
+    #[test]
+    fn test_exactly_once_terminates_on_cycles() {
+        let pool = ActorPool::new();
+        pool.set_propagation(Propagation::ExactlyOnce);
+
+        let a = pool.create_actor();
+        let b = pool.create_actor();
+        let c = pool.create_actor();
+
+        // a -> b -> c -> a
+        pool.subscribe(a, vec![b]).unwrap();
+        pool.subscribe(b, vec![c]).unwrap();
+        pool.subscribe(c, vec![a]).unwrap();
+
+        pool.message_loop(a, Message::Increment(1)).unwrap();
+
+        wait_for_value(&pool, c, 1);
+        thread::sleep(Duration::from_millis(100));
+        assert_eq!(pool.get_actor_value(a).unwrap(), 1);
+        assert_eq!(pool.get_actor_value(b).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_refused_copy_does_not_stop_propagation() {
+        let pool = ActorPool::new();
+        let publisher = pool.create_actor();
+        let paused = pool
+            .spawn_with_mailbox(Counter, MailboxConfig::default().with_stash_limit(Some(1)))
+            .unwrap()
+            .id();
+        let active = pool.create_actor();
+        pool.subscribe(publisher, vec![paused, active]).unwrap();
+        pool.pause_actor(paused).unwrap();
+
+        // the second copy for `paused` overflows its stash
+        pool.message_loop(publisher, Message::Increment(1)).unwrap();
+        pool.message_loop(publisher, Message::Increment(1)).unwrap();
+        pool.wait_idle();
+
+        assert_eq!(pool.get_actor_value(publisher).unwrap(), 2);
+        assert_eq!(pool.get_actor_value(active).unwrap(), 2);
+        assert_eq!(pool.get_stash_len(paused).unwrap(), 1);
+
+        let letters = pool.dead_letters();
+        assert_eq!(letters.len(), 1);
+        assert_eq!(letters[0].target, paused);
+    }
+
+    #[test]
+    fn test_long_chain_is_propagated_without_recursion() {
+        let pool = ActorPool::new();
+        let ids: Vec<usize> = (0..5_000).map(|_| pool.create_actor()).collect();
+
+        for pair in ids.windows(2) {
+            pool.subscribe(pair[0], vec![pair[1]]).unwrap();
+        }
+
+        pool.message_loop(ids[0], Message::Increment(1)).unwrap();
+
+        wait_for_value(&pool, *ids.last().unwrap(), 1);
+    }
+
+    #[test]
+    fn test_pool_max_depth() {
+        let pool = ActorPool::new();
+
+        let a = pool.create_actor();
+        let b = pool.create_actor();
+        let c = pool.create_actor();
+
+        pool.subscribe(a, vec![b]).unwrap();
+        pool.subscribe(b, vec![c]).unwrap();
+
+        pool.set_max_depth(Some(1));
+        assert_eq!(pool.max_depth(), Some(1));
+
+        pool.message_loop(a, Message::Increment(1)).unwrap();
+
+        wait_for_value(&pool, b, 1);
+        thread::sleep(Duration::from_millis(100));
+        assert_eq!(pool.get_actor_value(a).unwrap(), 1);
+        assert_eq!(pool.get_actor_value(c).unwrap(), 0);
+
+        // the limit of a message overrides the pool's
+        pool.message_loop_with_metadata(
+            a,
+            Message::Increment(1),
+            Metadata::new(a).with_max_depth(2),
+        )
+        .unwrap();
+        wait_for_value(&pool, c, 1);
+
+        pool.message_loop_with_metadata(
+            b,
+            Message::Increment(1),
+            Metadata::new(b).with_max_depth(0),
+        )
+        .unwrap();
+        wait_for_value(&pool, b, 3);
+        thread::sleep(Duration::from_millis(100));
+        assert_eq!(pool.get_actor_value(c).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_max_depth_bounds_per_path_cycles() {
+        let pool = ActorPool::new();
+
+        let a = pool.create_actor();
+        let b = pool.create_actor();
+
+        pool.subscribe(a, vec![b]).unwrap();
+        pool.subscribe(b, vec![a]).unwrap();
+
+        // a (0 hops) -> b (1) -> a (2) -> b (3)
+        pool.message_loop_with_metadata(
+            a,
+            Message::Increment(1),
+            Metadata::new(a).with_max_depth(3),
+        )
+        .unwrap();
+
+        wait_for_value(&pool, a, 2);
+        wait_for_value(&pool, b, 2);
+    }
+
+    /// Records the metadata of every message it handles.
+    #[derive(Debug, Default)]
+    struct Recorder;
+
+    impl Behavior for Recorder {
+        type State = Vec<Metadata>;
+        type Message = ();
+
+        fn initial_state(&self) -> Vec<Metadata> {
+            Vec::new()
+        }
+
+        fn handle(&self, _: &mut Vec<Metadata>, _: ()) -> Result<(), ActorError> {
+            unreachable!("handle_with_metadata is overridden")
+        }
+
+        fn handle_with_metadata(
+            &self,
+            state: &mut Vec<Metadata>,
+            _: (),
+            metadata: &Metadata,
+        ) -> Result<(), ActorError> {
+            state.push(*metadata);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_handlers_see_metadata() {
+        let pool: ActorPool<Recorder> = ActorPool::default();
+
+        let a = pool.create_actor();
+        let b = pool.create_actor();
+        let c = pool.create_actor();
+
+        pool.subscribe(a, vec![b]).unwrap();
+        pool.subscribe(b, vec![c]).unwrap();
+
+        let metadata = Metadata::new(a).with_trace_id(42);
+        pool.message_loop_with_metadata(a, (), metadata).unwrap();
+        pool.ask(c, ()).unwrap().wait().unwrap();
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while pool.get_actor_value(c).unwrap().len() < 2 && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(10));
+        }
+
+        let seen_by_a = pool.get_actor_value(a).unwrap();
+        let seen_by_c = pool.get_actor_value(c).unwrap();
+
+        assert_eq!(seen_by_a, vec![metadata]);
+        assert_eq!(pool.get_actor_value(b).unwrap()[0].hops, 1);
+
+        let propagated = seen_by_c.iter().find(|m| m.trace_id == 42).unwrap();
+        assert_eq!(propagated.origin, a);
+        assert_eq!(propagated.hops, 2);
+
+        // a message sent directly to an actor starts a new trace
+        let direct = seen_by_c.iter().find(|m| m.trace_id != 42).unwrap();
+        assert_eq!(direct.origin, c);
+        assert_eq!(direct.hops, 0);
+    }
+}
@@ -0,0 +1,150 @@
+#[cfg(test)]
+mod registry_tests {
+    use std::{thread, time::Duration};
+
+    use crate::model::{
+        actor::ActorPool,
+        behavior::Counter,
+        errors::ActorError,
+        lifecycle::StopMode,
+        message::Message,
+        supervisor::{RestartStrategy, SupervisorSpec},
+    };
+
+    #[test]
+    fn test_register_and_lookup() {
+        let pool = ActorPool::new();
+        let id = pool.create_actor();
+
+        pool.register("ledger", id).unwrap();
+        assert_eq!(pool.actor_name(id), Some("ledger".to_string()));
+
+        let ledger = pool.lookup("ledger").unwrap();
+        assert_eq!(ledger.id(), id);
+
+        ledger.ask(Message::Increment(3)).unwrap().wait().unwrap();
+        assert_eq!(pool.get_actor_value(id).unwrap(), 3);
+
+        assert!(matches!(
+            pool.lookup("missing"),
+            Err(ActorError::TargetActorNotFound(ref name)) if name == "missing"
+        ));
+
+        assert_eq!(pool.unregister("ledger").unwrap(), id);
+        assert!(pool.lookup("ledger").is_err());
+        assert_eq!(pool.actor_name(id), None);
+        assert!(pool.unregister("ledger").is_err());
+    }
+
+    #[test]
+    fn test_conflicting_names() {
+        let pool = ActorPool::new();
+        let first = pool.create_actor();
+        let second = pool.create_actor();
+
+        pool.register("/orders/shard-1", first).unwrap();
+        // registering the same actor under the same name again is harmless
+        pool.register("/orders/shard-1", first).unwrap();
+
+        assert!(matches!(
+            pool.register("/orders/shard-1", second),
+            Err(ActorError::ActorAlreadyExists(ref name)) if name == "/orders/shard-1"
+        ));
+        assert!(matches!(
+            pool.register("/orders/shard-2", first),
+            Err(ActorError::ActorAlreadyExists(_))
+        ));
+        assert!(matches!(
+            pool.spawn_named("/orders/shard-1", Counter),
+            Err(ActorError::ActorAlreadyExists(_))
+        ));
+
+        for name in ["", "two words", "a/b", "/orders/", "//orders", "/"] {
+            assert!(
+                matches!(
+                    pool.register(name, second),
+                    Err(ActorError::InvalidOperation(_))
+                ),
+                "{name:?} should be rejected"
+            );
+        }
+
+        assert!(matches!(
+            pool.register("ghost", usize::MAX),
+            Err(ActorError::TargetActorNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_hierarchical_names() {
+        let pool = ActorPool::new();
+
+        let shard1 = pool.spawn_named("/orders/shard-1", Counter).unwrap();
+        let shard3 = pool.spawn_named("/orders/eu/shard-3", Counter).unwrap();
+        pool.spawn_named("/orders-archive", Counter).unwrap();
+        pool.spawn_named("orders", Counter).unwrap();
+
+        let expected = vec![
+            ("/orders/eu/shard-3".to_string(), shard3.id()),
+            ("/orders/shard-1".to_string(), shard1.id()),
+        ];
+        assert_eq!(pool.registered_under("/orders"), expected);
+        assert_eq!(pool.registered_under("/orders/"), expected);
+        assert_eq!(
+            pool.registered_under("/orders/eu"),
+            vec![("/orders/eu/shard-3".to_string(), shard3.id())]
+        );
+        assert_eq!(pool.registered_under("/").len(), 3);
+    }
+
+    #[test]
+    fn test_stopped_actors_are_unregistered() {
+        let pool = ActorPool::new();
+
+        let stopped = pool.spawn_named("worker", Counter).unwrap();
+        pool.stop_actor(stopped.id(), StopMode::Drain).unwrap();
+
+        assert!(pool.lookup("worker").is_err());
+        assert!(matches!(
+            pool.register("worker", stopped.id()),
+            Err(ActorError::TargetActorIsOffline(_))
+        ));
+
+        // the name is free for a replacement
+        let failing = pool.spawn_named("worker", Counter).unwrap();
+        assert_eq!(pool.lookup("worker").unwrap().id(), failing.id());
+
+        failing.send(Message::Divide(0)).unwrap();
+        thread::sleep(Duration::from_millis(100));
+        assert!(pool.lookup("worker").is_err());
+
+        let removed = pool.spawn_named("worker", Counter).unwrap();
+        pool.remove_actor(removed.id(), StopMode::Drain).unwrap();
+        assert!(pool.lookup("worker").is_err());
+    }
+
+    #[test]
+    fn test_restarted_actors_keep_their_name() {
+        let pool = ActorPool::new();
+        let parent = pool.create_actor();
+        let child = pool.spawn_named("/parent/child", Counter).unwrap();
+
+        pool.supervise(
+            parent,
+            vec![child.id()],
+            SupervisorSpec::new(RestartStrategy::OneForOne),
+        )
+        .unwrap();
+
+        child.send(Message::Increment(5)).unwrap();
+        child.send(Message::Divide(0)).unwrap();
+        thread::sleep(Duration::from_millis(100));
+
+        let found = pool.lookup("/parent/child").unwrap();
+        assert_eq!(found.id(), child.id());
+        assert_eq!(found.value().unwrap(), 0);
+
+        pool.restart_actor(child.id()).unwrap();
+        assert_eq!(pool.lookup("/parent/child").unwrap().id(), child.id());
+    }
+}
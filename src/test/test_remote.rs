@@ -0,0 +1,198 @@
+#[cfg(test)]
+mod remote_tests {
+    use std::{net::SocketAddr, time::Duration};
+
+    use crate::model::{
+        actor::ActorPool, dead_letter::DeadLetterReason, errors::ActorError, message::Message,
+        remote::REMOTE_TIMEOUT, state::ActorState,
+    };
+    use crate::test::Sleeper;
+
+    fn localhost() -> SocketAddr {
+        "127.0.0.1:0".parse().unwrap()
+    }
+
+    #[test]
+    fn test_remote_actor() {
+        let server = ActorPool::new();
+        let id = server.create_actor();
+        let listener = server.listen(localhost()).unwrap();
+
+        let client = ActorPool::new();
+        client.create_actor();
+        let remote = client.remote_actor(listener.local_addr(), id).unwrap();
+        // the reference gets an id of its own pool
+        assert_eq!(remote.id(), 1);
+
+        client
+            .message_loop(remote.id(), Message::Increment(5))
+            .unwrap();
+        server.wait_idle();
+        assert_eq!(server.get_actor_value(id).unwrap(), 5);
+        assert_eq!(remote.value().unwrap(), 5);
+
+        assert_eq!(
+            remote.ask(Message::Multiply(3)).unwrap().wait().unwrap(),
+            15
+        );
+        assert!(matches!(
+            remote.ask(Message::Divide(0)).unwrap().wait(),
+            Err(ActorError::DividedByZero)
+        ));
+
+        server.pause_actor(id).unwrap();
+        assert_eq!(remote.state().unwrap(), ActorState::Paused);
+        assert_eq!(
+            client.get_actor_state(remote.id()).unwrap(),
+            ActorState::Paused
+        );
+
+        assert!(matches!(
+            client.remote_actor(listener.local_addr(), 9),
+            Err(ActorError::TargetActorNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_propagation_across_pools() {
+        let server = ActorPool::new();
+        let (first, second) = (server.create_actor(), server.create_actor());
+        server.subscribe(first, vec![second]).unwrap();
+        let listener = server.listen(localhost()).unwrap();
+
+        let client = ActorPool::new();
+        let local = client.create_actor();
+        let remote = client.remote_actor(listener.local_addr(), first).unwrap();
+        client.subscribe(local, vec![remote.id()]).unwrap();
+
+        // what the remote actor receives stays in its pool
+        let follower = client.create_actor();
+        assert!(matches!(
+            client.subscribe(remote.id(), vec![follower]),
+            Err(ActorError::InvalidOperation(_))
+        ));
+        assert!(matches!(
+            client.rewire(vec![(remote.id(), vec![follower])]),
+            Err(ActorError::InvalidOperation(_))
+        ));
+        assert!(client
+            .get_actor_subscribers(remote.id())
+            .unwrap()
+            .is_empty());
+
+        client.message_loop(local, Message::Increment(2)).unwrap();
+        client.wait_idle();
+        server.wait_idle();
+        assert_eq!(client.get_actor_value(local).unwrap(), 2);
+        assert_eq!(server.get_actor_value(first).unwrap(), 2);
+        assert_eq!(server.get_actor_value(second).unwrap(), 2);
+
+        // hops keep counting in the remote pool
+        client.set_max_depth(Some(1));
+        client.message_loop(local, Message::Increment(1)).unwrap();
+        client.wait_idle();
+        server.wait_idle();
+        assert_eq!(server.get_actor_value(first).unwrap(), 3);
+        assert_eq!(server.get_actor_value(second).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_offline_remote_actor() {
+        let server = ActorPool::new();
+        let id = server.create_actor();
+        let listener = server.listen(localhost()).unwrap();
+
+        let client = ActorPool::new();
+        let remote = client.remote_actor(listener.local_addr(), id).unwrap();
+        remote.send(Message::Increment(1)).unwrap();
+
+        listener.shutdown();
+
+        assert!(matches!(
+            remote.send(Message::Increment(1)),
+            Err(ActorError::TargetActorIsOffline(_))
+        ));
+        assert!(matches!(
+            remote.value(),
+            Err(ActorError::TargetActorIsOffline(_))
+        ));
+
+        let letters = client.dead_letters();
+        assert_eq!(letters.len(), 1);
+        assert_eq!(letters[0].target, remote.id());
+        assert_eq!(letters[0].message, Message::Increment(1));
+        assert!(matches!(letters[0].reason, DeadLetterReason::ActorOffline));
+    }
+
+    #[test]
+    fn test_paused_remote_actor() {
+        let server = ActorPool::new();
+        let id = server.create_actor();
+        let listener = server.listen(localhost()).unwrap();
+
+        let client = ActorPool::new();
+        let remote = client.remote_actor(listener.local_addr(), id).unwrap();
+
+        client.pause_actor(remote.id()).unwrap();
+        remote.send(Message::Increment(2)).unwrap();
+        let reply = remote.ask(Message::Multiply(5)).unwrap();
+        server.wait_idle();
+        assert_eq!(server.get_actor_value(id).unwrap(), 0);
+        assert_eq!(client.get_stash_len(remote.id()).unwrap(), 2);
+
+        client.resume_actor(remote.id()).unwrap();
+        assert_eq!(reply.wait().unwrap(), 10);
+        assert_eq!(server.get_actor_value(id).unwrap(), 10);
+
+        // timers would wait for the network on the timer thread
+        assert!(matches!(
+            client.send_after(remote.id(), Duration::from_millis(10), Message::Reset),
+            Err(ActorError::InvalidOperation(_))
+        ));
+        assert!(matches!(
+            client.send_interval(remote.id(), Duration::from_millis(10), Message::Reset),
+            Err(ActorError::InvalidOperation(_))
+        ));
+    }
+
+    #[test]
+    fn test_slow_remote_actor() {
+        let server = ActorPool::<Sleeper>::default();
+        let id = server.create_actor();
+        let listener = server.listen(localhost()).unwrap();
+
+        let client = ActorPool::<Sleeper>::default();
+        let remote = client.remote_actor(listener.local_addr(), id).unwrap();
+        assert_eq!(remote.ask(1).unwrap().wait().unwrap(), vec![1]);
+
+        // the remote pool stops waiting first, and the message is not sent again
+        let slow = REMOTE_TIMEOUT.as_millis() as u64 + 500;
+        assert!(matches!(
+            remote.ask(slow).unwrap().wait(),
+            Err(ActorError::Timeout(_))
+        ));
+        server.wait_idle();
+        assert_eq!(server.get_actor_value(id).unwrap(), vec![1, slow]);
+        assert_eq!(remote.value().unwrap(), vec![1, slow]);
+    }
+
+    #[test]
+    fn test_message_encoding() {
+        let messages = [
+            (Message::Increment(5), r#"{"Increment":5}"#),
+            (Message::Reset, r#""Reset""#),
+            (
+                Message::CompareAndSet {
+                    expected: 1,
+                    new: 2,
+                },
+                r#"{"CompareAndSet":{"expected":1,"new":2}}"#,
+            ),
+        ];
+
+        for (message, json) in messages {
+            assert_eq!(serde_json::to_string(&message).unwrap(), json);
+            assert_eq!(serde_json::from_str::<Message>(json).unwrap(), message);
+        }
+    }
+}
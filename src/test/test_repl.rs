@@ -0,0 +1,102 @@
+#[cfg(test)]
+mod repl_tests {
+    use std::io::Cursor;
+
+    use crate::{
+        cli::repl::{Repl, PROMPT},
+        model::{actor::ActorPool, state::ActorState},
+    };
+
+    /// Feed `input` to a REPL over `pool` and return everything it printed, prompts excluded.
+    fn session(pool: &ActorPool, input: &str) -> String {
+        let mut out = Vec::new();
+        Repl::new(pool).run(Cursor::new(input), &mut out).unwrap();
+
+        String::from_utf8(out).unwrap().replace(PROMPT, "")
+    }
+
+    #[test]
+    fn test_session_keeps_the_pool() {
+        let pool = ActorPool::new();
+        let output = session(
+            &pool,
+            "spawn 3\nsub 0 1 2\nsend 0 inc 10\nvalue 2\nunsub 0 2\nsend 0 dec 4\nvalue 1\nvalue 2\n",
+        );
+
+        assert_eq!(output, "0\n1\n2\n10\n6\n10\n\n");
+        assert_eq!(pool.get_actor_value(1).unwrap(), 6);
+    }
+
+    #[test]
+    fn test_errors_do_not_end_the_session() {
+        let pool = ActorPool::new();
+        let output = session(&pool, "value 0\njump\nrepl\nspawn\nvalue 0\nexit\nspawn\n");
+
+        let lines: Vec<&str> = output.lines().collect();
+        assert!(lines[0].starts_with("error: Target actor not found"));
+        assert!(output.contains("unrecognized subcommand 'jump'"));
+        assert!(output.contains("error: already in the REPL"));
+        // nothing runs after `exit`
+        assert!(output.ends_with("0\n0\n"));
+        assert_eq!(pool.snapshot().nodes.len(), 1);
+    }
+
+    #[test]
+    fn test_toggle_state_and_stash() {
+        let pool = ActorPool::new();
+        let output = session(
+            &pool,
+            "spawn\nstate 0 toggle\nsend 0 inc 5\nmailbox 0\nvalue 0\nstate 0 toggle\nvalue 0\nquit\n",
+        );
+
+        assert_eq!(output, "0\nPaused\nmailbox: 0\nstash: 1\n0\nActive\n5\n");
+        assert_eq!(pool.get_actor_state(0).unwrap(), ActorState::Active);
+    }
+
+    #[test]
+    fn test_mailbox_overflow() {
+        let pool = ActorPool::new();
+        let output = session(
+            &pool,
+            "spawn --capacity 1\nsend --times 20 0 inc 1\nspawn --unbounded\nsend -n 20 1 inc 1\nvalue 1\n",
+        );
+
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines[0], "0");
+        assert!(lines[1].ends_with("of 20 rejected"));
+        // the end of the input ends the line of the last prompt
+        assert_eq!(&lines[2..], ["1", "0 of 20 rejected", "20", ""]);
+
+        let rejected: usize = lines[1].split(' ').next().unwrap().parse().unwrap();
+        let value = pool.get_actor_value(0).unwrap() as usize;
+        assert_eq!(rejected + value, 20);
+        assert_eq!(pool.dead_letters_for(0).len(), rejected);
+    }
+
+    #[test]
+    fn test_history() {
+        let pool = ActorPool::new();
+        let mut repl = Repl::new(&pool);
+        let mut out = Vec::new();
+
+        for line in [
+            "spawn 2",
+            "",
+            "# a comment",
+            "  propagation exactly-once ",
+            "history",
+        ] {
+            assert!(repl.execute(line, &mut out).unwrap());
+        }
+        assert!(!repl.execute("exit", &mut out).unwrap());
+
+        assert_eq!(
+            repl.history(),
+            ["spawn 2", "propagation exactly-once", "history", "exit"]
+        );
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "0\n1\nExactlyOnce\n   1  spawn 2\n   2  propagation exactly-once\n   3  history\n"
+        );
+    }
+}
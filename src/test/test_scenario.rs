@@ -0,0 +1,150 @@
+#[cfg(test)]
+mod scenario_tests {
+    use crate::{
+        cli::scenario::{Scenario, ScenarioReport},
+        model::errors::ActorError,
+    };
+
+    fn run(scenario: &str) -> ScenarioReport {
+        scenario.parse::<Scenario>().unwrap().run().unwrap()
+    }
+
+    #[test]
+    fn test_diamond_scenario() {
+        let report = run("
+            # a diamond: actor 3 receives the message once per path
+            actors 4
+            edge 0 -> 1 2
+            edge 1 -> 3
+            edge 2 -> 3
+            send 0 inc 1
+            at 30ms send 3 mul 10
+
+            expect value 3 = 20
+            expect value 1 = 1
+            expect subscribers 0 = 2 1
+            expect subscribers 3 = none
+            expect state 3 = active
+            expect dead-letters = 0
+            expect cycles = 0
+        ");
+
+        assert!(report.passed(), "{report}");
+        assert_eq!(report.checks.len(), 7);
+        // lines are counted from the opening quote
+        assert_eq!(report.checks[0].line, 10);
+        assert!(report.to_string().ends_with("7 passed, 0 failed"));
+    }
+
+    #[test]
+    fn test_failed_expectations() {
+        let report = run("
+            actors 2
+            edge 0 -> 1
+            send 0 set 5
+            expect value 1 = 5
+            expect value 1 = 6
+            expect state 7 = Active
+        ");
+
+        assert!(!report.passed());
+
+        let failures = report.failures();
+        assert_eq!(failures.len(), 2);
+        assert_eq!(failures[0].line, 6);
+        assert_eq!(failures[0].subject, "value of 1");
+        assert_eq!(failures[0].expected, "6");
+        assert_eq!(failures[0].actual, "5");
+        assert!(failures[1]
+            .actual
+            .starts_with("error: Target actor not found"));
+
+        assert_eq!(
+            report.to_string(),
+            "ok   line 5: value of 1 = 5\n\
+             FAIL line 6: value of 1\n       expected: 6\n       actual:   5\n\
+             FAIL line 7: state of 7\n       expected: Active\n       actual:   error: Target actor not found: 7\n\
+             1 passed, 2 failed"
+        );
+    }
+
+    #[test]
+    fn test_cycles_and_propagation() {
+        let cycle = "
+            actors 3
+            edge 0 -> 1
+            edge 1 -> 2
+            edge 2 -> 0
+            send 0 inc 1
+            expect cycles = 1
+        ";
+
+        // per path, the message is refused before reaching any actor
+        let report = run(&format!("{cycle}\nexpect value 0 = 0\nexpect value 2 = 0"));
+        assert!(report.passed(), "{report}");
+
+        let report = run(&format!(
+            "propagation exactly-once\n{cycle}\nexpect value 1 = 1\nexpect value 2 = 1"
+        ));
+        assert!(report.passed(), "{report}");
+    }
+
+    #[test]
+    fn test_stash_and_overflow() {
+        let report = run("
+            actors 1
+            actors 1 capacity 1 overflow drop-newest
+            pause 0
+            send 0 inc 1
+            send 0 inc 2
+            expect stash 0 = 2
+            expect value 0 = 0
+            expect state 0 = Paused
+
+            pause 1
+            send 1 inc 1
+            send 1 inc 1
+            resume 1
+            settle
+            expect value 1 = 2
+            expect mailbox 1 = 0
+            expect dead-letters 1 = 0
+            expect dead-letters = 0
+        ");
+
+        assert!(report.passed(), "{report}");
+
+        // a message to a missing actor is a dead letter, not an error
+        let report = run("actors 1\nwait 1ms\nsend 7 inc 1\nexpect dead-letters 7 = 1");
+        assert!(report.passed(), "{report}");
+    }
+
+    #[test]
+    fn test_invalid_scenarios() {
+        for (scenario, line) in [
+            ("actors x", 1),
+            ("actors 1\nactors 1 capacity 0", 2),
+            ("actors 1 overflow sideways", 1),
+            ("\n\nedge 0 1", 3),
+            ("send 0 jump", 1),
+            ("at soon send 0 inc 1", 1),
+            ("expect value 0 5", 1),
+            ("expect state 0 = asleep", 1),
+            ("propagation twice", 1),
+            ("explode", 1),
+        ] {
+            let error = scenario.parse::<Scenario>().unwrap_err();
+            assert!(
+                matches!(error, ActorError::InvalidOperation(ref msg) if msg.starts_with(&format!("line {line}:"))),
+                "{scenario:?} gave {error}"
+            );
+        }
+
+        // statements that can't run stop the scenario
+        let scenario: Scenario = "actors 1\nedge 0 -> 4".parse().unwrap();
+        assert!(matches!(
+            scenario.run(),
+            Err(ActorError::InvalidOperation(ref msg)) if msg.starts_with("line 2:")
+        ));
+    }
+}
@@ -0,0 +1,132 @@
+#[cfg(test)]
+mod scheduler_tests {
+    use std::{
+        sync::{Arc, Mutex},
+        thread,
+        time::Duration,
+    };
+
+    use crate::model::{
+        actor::ActorPool, behavior::Behavior, errors::ActorError, message::Message,
+        scheduler::Scheduler,
+    };
+
+    /// Appends its tag to a shared log for every message, sleeping for the received milliseconds first.
+    #[derive(Debug)]
+    struct Logger {
+        tag: char,
+        log: Arc<Mutex<Vec<char>>>,
+    }
+
+    impl Behavior for Logger {
+        type State = ();
+        type Message = u64;
+
+        fn initial_state(&self) {}
+
+        fn handle(&self, _: &mut (), message: u64) -> Result<(), ActorError> {
+            thread::sleep(Duration::from_millis(message));
+            self.log.lock().unwrap().push(self.tag);
+
+            Ok(())
+        }
+    }
+
+    fn run_interleaving(budget: usize) -> Vec<char> {
+        let scheduler = Arc::new(Scheduler::new(1, budget).unwrap());
+        let pool = ActorPool::<Logger>::with_scheduler(scheduler);
+        let log = Arc::new(Mutex::new(Vec::new()));
+
+        let spawn = |tag| {
+            pool.spawn(Logger {
+                tag,
+                log: Arc::clone(&log),
+            })
+        };
+        let gate = spawn('g');
+        let a = spawn('a');
+        let b = spawn('b');
+
+        // keep the only worker busy until both mailboxes are filled
+        gate.send(50).unwrap();
+        for _ in 0..3 {
+            a.send(0).unwrap();
+        }
+        for _ in 0..3 {
+            b.send(0).unwrap();
+        }
+
+        let reply_a = a.ask(0).unwrap();
+        let reply_b = b.ask(0).unwrap();
+
+        reply_a.wait().unwrap();
+        reply_b.wait().unwrap();
+
+        let log = log.lock().unwrap().clone();
+        log
+    }
+
+    #[test]
+    fn test_message_budget_gives_every_actor_a_turn() {
+        let log: String = run_interleaving(1).into_iter().collect();
+
+        assert_eq!(log, "gabababab");
+    }
+
+    #[test]
+    fn test_large_budget_drains_mailbox_in_one_activation() {
+        let log: String = run_interleaving(10).into_iter().collect();
+
+        assert_eq!(log, "gaaaabbbb");
+    }
+
+    #[test]
+    fn test_many_actors_on_few_workers() {
+        let scheduler = Arc::new(Scheduler::new(2, 8).unwrap());
+        let pool: ActorPool = ActorPool::with_scheduler(Arc::clone(&scheduler));
+
+        let actors: Vec<_> = (0..10_000)
+            .map(|_| pool.spawn(Default::default()))
+            .collect();
+
+        for actor in actors.iter() {
+            actor.send(Message::Increment(1)).unwrap();
+        }
+
+        for actor in actors.iter() {
+            assert_eq!(actor.ask(Message::Increment(1)).unwrap().wait().unwrap(), 2);
+        }
+
+        assert_eq!(scheduler.num_workers(), 2);
+    }
+
+    #[test]
+    fn test_pools_share_a_scheduler() {
+        let scheduler = Arc::new(Scheduler::new(1, 4).unwrap());
+
+        let pool1: ActorPool = ActorPool::with_scheduler(Arc::clone(&scheduler));
+        let pool2: ActorPool = ActorPool::with_scheduler(Arc::clone(&scheduler));
+
+        let a1 = pool1.create_actor();
+        let a2 = pool2.create_actor();
+
+        let r1 = pool1.ask(a1, Message::Increment(1)).unwrap();
+        let r2 = pool2.ask(a2, Message::Increment(2)).unwrap();
+
+        assert_eq!(r1.wait().unwrap(), 1);
+        assert_eq!(r2.wait().unwrap(), 2);
+        assert!(Arc::ptr_eq(&pool1.scheduler, &pool2.scheduler));
+    }
+
+    #[test]
+    fn test_invalid_scheduler_configuration() {
+        assert!(matches!(
+            Scheduler::new(0, 1),
+            Err(ActorError::InvalidOperation(_))
+        ));
+        assert!(matches!(
+            Scheduler::new(1, 0),
+            Err(ActorError::InvalidOperation(_))
+        ));
+    }
+}
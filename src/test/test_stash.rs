@@ -0,0 +1,155 @@
+#[cfg(test)]
+mod stash_tests {
+    use std::{
+        thread,
+        time::{Duration, Instant},
+    };
+
+    use crate::model::{
+        actor::ActorPool,
+        dead_letter::DeadLetterReason,
+        errors::ActorError,
+        lifecycle::StopMode,
+        mailbox::{MailboxConfig, STASH_LIMIT},
+        message::Message,
+        state::ActorState,
+    };
+    use crate::test::Sleeper;
+
+    #[test]
+    fn test_stash_is_replayed_in_order() {
+        let pool = ActorPool::new();
+        let actor = pool.create_actor();
+
+        pool.update_actor_state(actor).unwrap();
+
+        // only this order gives 7
+        pool.message_loop(actor, Message::Set(2)).unwrap();
+        pool.message_loop(actor, Message::Multiply(3)).unwrap();
+        pool.message_loop(actor, Message::Increment(1)).unwrap();
+
+        thread::sleep(Duration::from_millis(50));
+        assert_eq!(pool.get_actor_value(actor).unwrap(), 0);
+        assert_eq!(pool.get_stash_len(actor).unwrap(), 3);
+        assert_eq!(pool.get_mailbox_len(actor).unwrap(), 0);
+
+        assert_eq!(pool.update_actor_state(actor).unwrap(), ActorState::Active);
+        pool.wait_idle();
+        assert_eq!(pool.get_actor_value(actor).unwrap(), 7);
+        assert_eq!(pool.get_stash_len(actor).unwrap(), 0);
+
+        // messages sent after resuming are handled as usual
+        pool.message_loop(actor, Message::Increment(1)).unwrap();
+        thread::sleep(Duration::from_millis(50));
+        assert_eq!(pool.get_actor_value(actor).unwrap(), 8);
+    }
+
+    #[test]
+    fn test_stash_limit() {
+        let pool = ActorPool::<Sleeper>::default();
+        let actor = pool
+            .spawn_with_mailbox(Sleeper, MailboxConfig::default().with_stash_limit(Some(2)))
+            .unwrap();
+
+        assert_eq!(MailboxConfig::default().stash_limit, Some(STASH_LIMIT));
+
+        pool.update_actor_state(actor.id()).unwrap();
+        actor.send(1).unwrap();
+        actor.send(2).unwrap();
+
+        assert!(matches!(
+            actor.send(3),
+            Err(ActorError::StashOverflow(ref id)) if *id == actor.id().to_string()
+        ));
+
+        let letters = pool.dead_letters_for(actor.id());
+        assert_eq!(letters.len(), 1);
+        assert_eq!(letters[0].message, 3);
+        assert!(matches!(letters[0].reason, DeadLetterReason::StashOverflow));
+
+        pool.update_actor_state(actor.id()).unwrap();
+        pool.wait_idle();
+        assert_eq!(actor.value().unwrap(), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_inspect_and_discard_the_stash() {
+        let pool = ActorPool::new();
+        let actor = pool.create_actor();
+
+        pool.update_actor_state(actor).unwrap();
+        pool.message_loop(actor, Message::Increment(1)).unwrap();
+        pool.message_loop(actor, Message::Decrement(2)).unwrap();
+
+        assert_eq!(
+            pool.get_stashed_messages(actor).unwrap(),
+            vec![Message::Increment(1), Message::Decrement(2)]
+        );
+
+        let discarded = pool.discard_stash(actor).unwrap();
+        assert_eq!(
+            discarded,
+            vec![Message::Increment(1), Message::Decrement(2)]
+        );
+        assert!(pool.get_stashed_messages(actor).unwrap().is_empty());
+
+        let letters = pool.dead_letters_for(actor);
+        assert_eq!(letters.len(), 2);
+        assert!(letters
+            .iter()
+            .all(|letter| matches!(letter.reason, DeadLetterReason::Discarded)));
+
+        pool.update_actor_state(actor).unwrap();
+        assert_eq!(pool.get_actor_value(actor).unwrap(), 0);
+
+        assert!(matches!(
+            pool.get_stash_len(usize::MAX),
+            Err(ActorError::TargetActorNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_pending_messages_are_stashed_when_pausing() {
+        let pool = ActorPool::<Sleeper>::default();
+        let actor = pool.spawn(Sleeper);
+
+        // keep the actor busy, so that the next messages wait in its mailbox
+        actor.send(100).unwrap();
+        thread::sleep(Duration::from_millis(20));
+        actor.send(1).unwrap();
+        actor.send(2).unwrap();
+
+        pool.update_actor_state(actor.id()).unwrap();
+        assert_eq!(pool.get_mailbox_len(actor.id()).unwrap(), 0);
+        assert_eq!(pool.get_stashed_messages(actor.id()).unwrap(), vec![1, 2]);
+
+        // the message being handled is not interrupted
+        thread::sleep(Duration::from_millis(150));
+        assert_eq!(actor.value().unwrap(), vec![100]);
+
+        pool.update_actor_state(actor.id()).unwrap();
+        actor.send(3).unwrap();
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while actor.value().unwrap().len() < 4 && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(actor.value().unwrap(), vec![100, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_stopping_drops_the_stash() {
+        let pool = ActorPool::new();
+        let actor = pool.create_actor();
+
+        pool.update_actor_state(actor).unwrap();
+        pool.message_loop(actor, Message::Increment(1)).unwrap();
+
+        let report = pool.remove_actor(actor, StopMode::Drain).unwrap();
+        assert_eq!(report.dropped, vec![Message::Increment(1)]);
+        assert!(matches!(
+            pool.dead_letters()[0].reason,
+            DeadLetterReason::Discarded
+        ));
+    }
+}
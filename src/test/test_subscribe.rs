@@ -1,6 +1,6 @@
 #[cfg(test)]
 mod actor_subscribe_system_test {
-    use crate::model::actor::ActorPool;
+    use crate::model::{actor::ActorPool, errors::ActorError, message::Message};
 
     #[test]
     fn test_get_default_actor_subscribe_list() {
@@ -70,7 +70,7 @@ mod actor_subscribe_system_test {
 
         let has_cycle = pool.detect_cycle_bfs(a1).unwrap();
 
-        assert_eq!(has_cycle, true)
+        assert!(has_cycle)
     }
 
     #[test]
@@ -87,7 +87,7 @@ mod actor_subscribe_system_test {
 
         let has_cycle = pool.detect_cycle_bfs(a1).unwrap();
 
-        assert_eq!(has_cycle, false)
+        assert!(!has_cycle)
     }
 
     #[test]
@@ -105,7 +105,7 @@ mod actor_subscribe_system_test {
 
         let has_cycle = pool.detect_cycle_dfs(a1).unwrap();
 
-        assert_eq!(has_cycle, true)
+        assert!(has_cycle)
     }
 
     #[test]
@@ -122,7 +122,7 @@ mod actor_subscribe_system_test {
 
         let has_cycle = pool.detect_cycle_dfs(a1).unwrap();
 
-        assert_eq!(has_cycle, false)
+        assert!(!has_cycle)
     }
 
     #[test]
@@ -138,7 +138,32 @@ mod actor_subscribe_system_test {
 
         let has_cycle = pool.detect_cycle_bfs(a1).unwrap();
 
-        assert_eq!(has_cycle, false)
+        assert!(!has_cycle)
+    }
+
+    #[test]
+    fn test_diamond_is_not_a_cycle() {
+        let pool = ActorPool::new();
+
+        let a1 = pool.create_actor();
+        let a2 = pool.create_actor();
+        let a3 = pool.create_actor();
+        let a4 = pool.create_actor();
+
+        // a1 reaches a4 through both a2 and a3
+        pool.subscribe(a1, vec![a2, a3]).unwrap();
+        pool.subscribe(a2, vec![a4]).unwrap();
+        pool.subscribe(a3, vec![a4]).unwrap();
+
+        assert!(!pool.detect_cycle_dfs(a1).unwrap());
+        assert!(!pool.detect_cycle_bfs(a1).unwrap());
+        assert!(!pool.detect_cycle_topological_sort(a1).unwrap());
+
+        pool.subscribe(a4, vec![a1]).unwrap();
+
+        assert!(pool.detect_cycle_dfs(a1).unwrap());
+        assert!(pool.detect_cycle_bfs(a1).unwrap());
+        assert!(pool.detect_cycle_topological_sort(a1).unwrap());
     }
 
     #[test]
@@ -156,6 +181,261 @@ mod actor_subscribe_system_test {
 
         let has_cycle = pool.detect_cycle_topological_sort(a1).unwrap();
 
-        assert_eq!(has_cycle, true)
+        assert!(has_cycle)
+    }
+
+    #[test]
+    fn test_reject_subscription_creating_cycle() {
+        let pool = ActorPool::new();
+        pool.set_reject_cycles(true);
+
+        let a1 = pool.create_actor();
+        let a2 = pool.create_actor();
+        let a3 = pool.create_actor();
+
+        pool.subscribe(a1, vec![a2]).unwrap();
+        pool.subscribe(a2, vec![a3]).unwrap();
+
+        // a3 -> a1 would close the loop a1 -> a2 -> a3 -> a1
+        let result = pool.subscribe(a3, vec![a1]);
+        assert!(matches!(
+            result,
+            Err(ActorError::CycleDetected(ref path)) if *path == vec![a3, a1, a2, a3]
+        ));
+
+        assert!(pool.get_actor_subscribers(a3).unwrap().is_empty());
+        assert!(!pool.detect_cycle_topological_sort(a1).unwrap());
+    }
+
+    #[test]
+    fn test_reject_cycles_leaves_graph_unchanged() {
+        let pool = ActorPool::new();
+        pool.set_reject_cycles(true);
+
+        let a1 = pool.create_actor();
+        let a2 = pool.create_actor();
+        let a3 = pool.create_actor();
+        let a4 = pool.create_actor();
+
+        pool.subscribe(a1, vec![a2]).unwrap();
+
+        // a4 is fine, but a1 is reachable from a2: nothing must be added
+        let result = pool.subscribe(a2, vec![a4, a3, a1]);
+        assert!(matches!(result, Err(ActorError::CycleDetected(_))));
+        assert!(pool.get_actor_subscribers(a2).unwrap().is_empty());
+
+        // unknown or duplicated subscribers don't leave partial subscriptions either
+        assert!(matches!(
+            pool.subscribe(a2, vec![a3, usize::MAX]),
+            Err(ActorError::TargetActorNotFound(_))
+        ));
+        assert!(matches!(
+            pool.subscribe(a2, vec![a3, a3]),
+            Err(ActorError::ActorAlreadyExists(_))
+        ));
+        assert!(pool.get_actor_subscribers(a2).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_reject_cycles_through_actor_refs() {
+        let pool = ActorPool::new();
+        pool.set_reject_cycles(true);
+
+        let a = pool.spawn(Default::default());
+        let b = pool.spawn(Default::default());
+
+        pool.subscribe(a.id(), vec![b.id()]).unwrap();
+
+        assert!(matches!(
+            b.subscribe(std::slice::from_ref(&a)),
+            Err(ActorError::CycleDetected(ref path)) if *path == vec![b.id(), a.id(), b.id()]
+        ));
+        assert!(b.subscribers().unwrap().is_empty());
+        assert!(pool.find_cycles().is_empty());
+    }
+
+    #[test]
+    fn test_reject_self_subscription() {
+        let pool = ActorPool::new();
+        pool.set_reject_cycles(true);
+
+        let a1 = pool.create_actor();
+
+        assert!(matches!(
+            pool.subscribe(a1, vec![a1]),
+            Err(ActorError::CycleDetected(ref path)) if *path == vec![a1, a1]
+        ));
+    }
+
+    #[test]
+    fn test_reject_cycles_allows_diamonds() {
+        let pool = ActorPool::new();
+        pool.set_reject_cycles(true);
+
+        let a1 = pool.create_actor();
+        let a2 = pool.create_actor();
+        let a3 = pool.create_actor();
+        let a4 = pool.create_actor();
+
+        pool.subscribe(a1, vec![a2, a3]).unwrap();
+        pool.subscribe(a2, vec![a4]).unwrap();
+        pool.subscribe(a3, vec![a4]).unwrap();
+
+        assert_eq!(pool.get_actor_subscribers(a3).unwrap(), vec![a4]);
+    }
+
+    #[test]
+    fn test_cycles_are_allowed_by_default() {
+        let pool = ActorPool::new();
+
+        let a1 = pool.create_actor();
+        let a2 = pool.create_actor();
+
+        pool.subscribe(a1, vec![a2]).unwrap();
+        pool.subscribe(a2, vec![a1]).unwrap();
+
+        assert!(pool.detect_cycle_dfs(a1).unwrap());
+    }
+
+    #[test]
+    fn test_unsubscribe() {
+        let pool = ActorPool::new();
+
+        let a1 = pool.create_actor();
+        let a2 = pool.create_actor();
+        let a3 = pool.create_actor();
+
+        pool.subscribe(a1, vec![a2, a3]).unwrap();
+
+        let target = pool.unsubscribe(a1, vec![a2]).unwrap();
+        assert_eq!(target.get_subscribers(), vec![a3]);
+
+        // a message no longer reaches the unsubscribed actor
+        pool.ask(a1, Message::Increment(1)).unwrap().wait().unwrap();
+        assert_eq!(
+            pool.ask(a2, Message::Increment(0)).unwrap().wait().unwrap(),
+            0
+        );
+        assert_eq!(
+            pool.ask(a3, Message::Increment(0)).unwrap().wait().unwrap(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_unsubscribe_non_subscriber() {
+        let pool = ActorPool::new();
+
+        let a1 = pool.create_actor();
+        let a2 = pool.create_actor();
+        let a3 = pool.create_actor();
+
+        pool.subscribe(a1, vec![a2]).unwrap();
+
+        assert!(matches!(
+            pool.unsubscribe(a1, vec![a2, a3]),
+            Err(ActorError::NotInSubscriberList(ref sub, ref target))
+                if *sub == a3.to_string() && *target == a1.to_string()
+        ));
+        assert!(matches!(
+            pool.unsubscribe(a1, vec![a2, a2]),
+            Err(ActorError::NotInSubscriberList(_, _))
+        ));
+        assert!(matches!(
+            pool.unsubscribe(usize::MAX, vec![a2]),
+            Err(ActorError::TargetActorNotFound(_))
+        ));
+
+        // nothing was removed by the failed calls
+        assert_eq!(pool.get_actor_subscribers(a1).unwrap(), vec![a2]);
+    }
+
+    #[test]
+    fn test_clear_subscribers() {
+        let pool = ActorPool::new();
+
+        let a1 = pool.create_actor();
+        let a2 = pool.create_actor();
+        let a3 = pool.create_actor();
+
+        pool.subscribe(a1, vec![a3, a2]).unwrap();
+
+        assert_eq!(pool.clear_subscribers(a1).unwrap(), vec![a2, a3]);
+        assert!(pool.get_actor_subscribers(a1).unwrap().is_empty());
+        assert!(pool.clear_subscribers(a1).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_get_publishers() {
+        let pool = ActorPool::new();
+
+        let a1 = pool.create_actor();
+        let a2 = pool.create_actor();
+        let a3 = pool.create_actor();
+
+        pool.subscribe(a1, vec![a3]).unwrap();
+        pool.subscribe(a2, vec![a3]).unwrap();
+
+        assert_eq!(pool.get_publishers(a3).unwrap(), vec![a1, a2]);
+        assert!(pool.get_publishers(a1).unwrap().is_empty());
+        assert!(matches!(
+            pool.get_publishers(usize::MAX),
+            Err(ActorError::TargetActorNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_rewire() {
+        let pool = ActorPool::new();
+
+        let a1 = pool.create_actor();
+        let a2 = pool.create_actor();
+        let a3 = pool.create_actor();
+
+        pool.subscribe(a1, vec![a2]).unwrap();
+        pool.subscribe(a2, vec![a3]).unwrap();
+
+        // reverse the chain: a3 -> a2 -> a1
+        pool.rewire(vec![(a1, vec![]), (a2, vec![a1]), (a3, vec![a2])])
+            .unwrap();
+
+        assert!(pool.get_actor_subscribers(a1).unwrap().is_empty());
+        assert_eq!(pool.get_actor_subscribers(a2).unwrap(), vec![a1]);
+        assert_eq!(pool.get_actor_subscribers(a3).unwrap(), vec![a2]);
+    }
+
+    #[test]
+    fn test_rewire_is_atomic() {
+        let pool = ActorPool::new();
+        pool.set_reject_cycles(true);
+
+        let a1 = pool.create_actor();
+        let a2 = pool.create_actor();
+        let a3 = pool.create_actor();
+
+        pool.subscribe(a1, vec![a2]).unwrap();
+
+        // a2 -> a1 closes a cycle with the existing a1 -> a2 edge
+        assert!(matches!(
+            pool.rewire(vec![(a3, vec![a1]), (a2, vec![a1])]),
+            Err(ActorError::CycleDetected(_))
+        ));
+
+        // unknown ids and repeated targets are rejected as well
+        assert!(matches!(
+            pool.rewire(vec![(a3, vec![a1]), (a2, vec![usize::MAX])]),
+            Err(ActorError::TargetActorNotFound(_))
+        ));
+        assert!(matches!(
+            pool.rewire(vec![(a3, vec![a1]), (a3, vec![a2])]),
+            Err(ActorError::InvalidOperation(_))
+        ));
+
+        assert!(pool.get_actor_subscribers(a3).unwrap().is_empty());
+        assert!(pool.get_actor_subscribers(a2).unwrap().is_empty());
+
+        // replacing the edge that would close the cycle is fine
+        pool.rewire(vec![(a1, vec![]), (a2, vec![a1])]).unwrap();
+        assert_eq!(pool.get_publishers(a1).unwrap(), vec![a2]);
     }
 }
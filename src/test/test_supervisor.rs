@@ -0,0 +1,226 @@
+#[cfg(test)]
+mod supervisor_tests {
+    use std::time::Duration;
+
+    use crate::model::{
+        actor::ActorPool,
+        behavior::Behavior,
+        errors::ActorError,
+        supervisor::{RestartStrategy, SupervisorAction, SupervisorSpec},
+    };
+
+    #[derive(Debug, Clone)]
+    enum Op {
+        Add(i32),
+        Fail,
+        Panic,
+    }
+
+    #[derive(Debug, Default)]
+    struct Fragile;
+
+    impl Behavior for Fragile {
+        type State = i32;
+        type Message = Op;
+
+        fn initial_state(&self) -> i32 {
+            0
+        }
+
+        fn handle(&self, state: &mut i32, message: Op) -> Result<(), ActorError> {
+            match message {
+                Op::Add(n) => *state += n,
+                Op::Fail => return Err(ActorError::InvalidMessage("fail".to_string())),
+                Op::Panic => panic!("handler exploded"),
+            }
+
+            Ok(())
+        }
+    }
+
+    fn add(pool: &ActorPool<Fragile>, id: usize, n: i32) -> i32 {
+        pool.ask(id, Op::Add(n)).unwrap().wait().unwrap()
+    }
+
+    /// Create a supervisor with three children, each holding the value 10.
+    fn supervised(spec: SupervisorSpec<i32>) -> (ActorPool<Fragile>, usize, Vec<usize>) {
+        let pool = ActorPool::<Fragile>::default();
+
+        let parent = pool.create_actor();
+        let children: Vec<usize> = (0..3).map(|_| pool.create_actor()).collect();
+
+        pool.supervise(parent, children.clone(), spec).unwrap();
+
+        for &child in children.iter() {
+            add(&pool, child, 10);
+        }
+
+        (pool, parent, children)
+    }
+
+    fn values(pool: &ActorPool<Fragile>, ids: &[usize]) -> Vec<i32> {
+        ids.iter()
+            .map(|&id| pool.get_actor_value(id).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_unsupervised_failure_stops_actor() {
+        let pool = ActorPool::<Fragile>::default();
+        let id = pool.create_actor();
+
+        pool.message_loop(id, Op::Fail).unwrap();
+
+        let result = pool.ask(id, Op::Add(1)).and_then(|reply| reply.wait());
+        assert!(matches!(result, Err(ActorError::TargetActorIsOffline(_))));
+    }
+
+    #[test]
+    fn test_one_for_one_restarts_failed_child_only() {
+        let (pool, parent, children) = supervised(SupervisorSpec::new(RestartStrategy::OneForOne));
+
+        pool.message_loop(children[1], Op::Fail).unwrap();
+
+        // the restarted child keeps handling its mailbox
+        assert_eq!(add(&pool, children[1], 1), 1);
+        assert_eq!(values(&pool, &children), vec![10, 1, 10]);
+
+        let supervisor = pool.get_supervisor(parent).unwrap();
+        let failures = supervisor.failures();
+
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].child_id, children[1]);
+        assert!(matches!(failures[0].error, ActorError::InvalidMessage(_)));
+        assert_eq!(
+            failures[0].action,
+            SupervisorAction::Restarted(vec![children[1]])
+        );
+        assert_eq!(supervisor.recent_restarts(), 1);
+    }
+
+    #[test]
+    fn test_one_for_all_restarts_every_child() {
+        let (pool, _, children) = supervised(SupervisorSpec::new(RestartStrategy::OneForAll));
+
+        pool.message_loop(children[1], Op::Fail).unwrap();
+        add(&pool, children[1], 0);
+
+        assert_eq!(values(&pool, &children), vec![0, 0, 0]);
+    }
+
+    #[test]
+    fn test_rest_for_one_restarts_later_children() {
+        let (pool, _, children) = supervised(SupervisorSpec::new(RestartStrategy::RestForOne));
+
+        pool.message_loop(children[1], Op::Fail).unwrap();
+        add(&pool, children[1], 0);
+
+        assert_eq!(values(&pool, &children), vec![10, 0, 0]);
+    }
+
+    #[test]
+    fn test_restart_with_state_factory() {
+        let spec = SupervisorSpec::new(RestartStrategy::OneForOne).with_factory(|| 42);
+        let (pool, _, children) = supervised(spec);
+
+        pool.message_loop(children[0], Op::Fail).unwrap();
+
+        assert_eq!(add(&pool, children[0], 0), 42);
+    }
+
+    #[test]
+    fn test_panics_are_reported_to_supervisor() {
+        let (pool, parent, children) = supervised(SupervisorSpec::new(RestartStrategy::OneForOne));
+
+        pool.message_loop(children[2], Op::Panic).unwrap();
+        assert_eq!(add(&pool, children[2], 5), 5);
+
+        let failures = pool.get_supervisor(parent).unwrap().failures();
+        assert!(matches!(
+            failures[0].error,
+            ActorError::ActorPanicked(ref message) if message == "handler exploded"
+        ));
+    }
+
+    #[test]
+    fn test_exceeding_restart_intensity_escalates() {
+        let pool = ActorPool::<Fragile>::default();
+
+        let root = pool.create_actor();
+        let parent = pool.create_actor();
+        let child = pool.create_actor();
+
+        pool.supervise(
+            root,
+            vec![parent],
+            SupervisorSpec::new(RestartStrategy::OneForOne),
+        )
+        .unwrap();
+        pool.supervise(
+            parent,
+            vec![child],
+            SupervisorSpec::new(RestartStrategy::OneForOne)
+                .with_intensity(2, Duration::from_secs(60)),
+        )
+        .unwrap();
+
+        for _ in 0..2 {
+            pool.message_loop(child, Op::Fail).unwrap();
+            add(&pool, child, 0);
+        }
+
+        pool.message_loop(child, Op::Fail).unwrap();
+        let result = pool.ask(child, Op::Add(0)).and_then(|reply| reply.wait());
+        assert!(matches!(result, Err(ActorError::TargetActorIsOffline(_))));
+
+        let failures = pool.get_supervisor(parent).unwrap().failures();
+        assert_eq!(failures.len(), 3);
+        assert_eq!(failures[2].action, SupervisorAction::Escalated);
+
+        // the parent's failure was handled by the root supervisor
+        let failures = pool.get_supervisor(root).unwrap().failures();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].child_id, parent);
+        assert!(matches!(
+            failures[0].error,
+            ActorError::RestartIntensityExceeded(_)
+        ));
+    }
+
+    #[test]
+    fn test_invalid_supervision() {
+        let pool = ActorPool::<Fragile>::default();
+
+        let parent = pool.create_actor();
+        let child = pool.create_actor();
+
+        assert!(matches!(
+            pool.supervise(
+                parent,
+                vec![parent],
+                SupervisorSpec::new(RestartStrategy::OneForOne)
+            ),
+            Err(ActorError::InvalidOperation(_))
+        ));
+
+        pool.supervise(
+            parent,
+            vec![child],
+            SupervisorSpec::new(RestartStrategy::OneForOne),
+        )
+        .unwrap();
+
+        assert!(matches!(
+            pool.supervise(
+                parent,
+                vec![],
+                SupervisorSpec::new(RestartStrategy::OneForAll)
+            ),
+            Err(ActorError::ActorAlreadyExists(_))
+        ));
+        assert!(matches!(
+            pool.get_supervisor(child),
+            Err(ActorError::InvalidOperation(_))
+        ));
+    }
+}
@@ -0,0 +1,152 @@
+#[cfg(test)]
+mod timer_tests {
+    use std::{
+        thread,
+        time::{Duration, Instant},
+    };
+
+    use crate::model::{
+        actor::ActorPool, dead_letter::DeadLetterReason, errors::ActorError, lifecycle::StopMode,
+        message::Message,
+    };
+
+    fn wait_until(condition: impl Fn() -> bool) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+
+        while !condition() && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(5));
+        }
+    }
+
+    #[test]
+    fn test_send_after() {
+        let pool = ActorPool::new();
+        let id = pool.create_actor();
+
+        let started = Instant::now();
+        let timer = pool
+            .send_after(id, Duration::from_millis(50), Message::Increment(1))
+            .unwrap();
+        assert!(timer.is_pending());
+
+        wait_until(|| pool.get_actor_value(id).unwrap() == 1);
+
+        assert!(started.elapsed() >= Duration::from_millis(50));
+        assert_eq!(pool.get_actor_value(id).unwrap(), 1);
+
+        wait_until(|| !timer.is_pending());
+        assert_eq!(timer.fired(), 1);
+        // a fired one-shot timer can't be cancelled anymore
+        assert!(!timer.cancel());
+    }
+
+    #[test]
+    fn test_cancel_before_firing() {
+        let pool = ActorPool::new();
+        let id = pool.create_actor();
+
+        let timer = pool
+            .send_after(id, Duration::from_millis(50), Message::Increment(1))
+            .unwrap();
+
+        assert!(timer.cancel());
+        assert!(!timer.cancel());
+        assert!(timer.is_cancelled());
+
+        thread::sleep(Duration::from_millis(150));
+        assert_eq!(pool.get_actor_value(id).unwrap(), 0);
+        assert_eq!(timer.fired(), 0);
+    }
+
+    #[test]
+    fn test_send_interval() {
+        let pool = ActorPool::new();
+        let id = pool.create_actor();
+
+        let timer = pool
+            .send_interval(id, Duration::from_millis(20), Message::Increment(1))
+            .unwrap();
+
+        wait_until(|| pool.get_actor_value(id).unwrap() >= 3);
+        assert!(timer.fired() >= 3);
+
+        assert!(timer.cancel());
+        // a delivery may be on its way while cancelling
+        thread::sleep(Duration::from_millis(50));
+        let value = pool.get_actor_value(id).unwrap();
+
+        thread::sleep(Duration::from_millis(100));
+        assert_eq!(pool.get_actor_value(id).unwrap(), value);
+        assert!(!timer.is_pending());
+    }
+
+    #[test]
+    fn test_many_timers() {
+        let pool = ActorPool::new();
+        let ids: Vec<usize> = (0..50).map(|_| pool.create_actor()).collect();
+
+        let timers: Vec<_> = ids
+            .iter()
+            .enumerate()
+            .map(|(n, &id)| {
+                let delay = Duration::from_millis(10 + (n as u64 % 5) * 10);
+                pool.send_after(id, delay, Message::Increment(1)).unwrap()
+            })
+            .collect();
+
+        wait_until(|| timers.iter().all(|timer| !timer.is_pending()));
+
+        for id in ids {
+            wait_until(|| pool.get_actor_value(id).unwrap() == 1);
+            assert_eq!(pool.get_actor_value(id).unwrap(), 1);
+        }
+    }
+
+    #[test]
+    fn test_interval_stops_with_its_actor() {
+        let pool = ActorPool::new();
+        let id = pool.create_actor();
+
+        let timer = pool
+            .send_interval(id, Duration::from_millis(10), Message::Increment(1))
+            .unwrap();
+        pool.remove_actor(id, StopMode::Drain).unwrap();
+
+        wait_until(|| !timer.is_pending());
+        assert!(!timer.is_pending());
+        assert!(!timer.is_cancelled());
+        assert!(matches!(
+            pool.dead_letters()[0].reason,
+            DeadLetterReason::ActorNotFound
+        ));
+    }
+
+    #[test]
+    fn test_invalid_timers() {
+        let pool = ActorPool::new();
+        let id = pool.create_actor();
+
+        assert!(matches!(
+            pool.send_interval(id, Duration::ZERO, Message::Increment(1)),
+            Err(ActorError::InvalidOperation(_))
+        ));
+        assert!(matches!(
+            pool.send_after(usize::MAX, Duration::ZERO, Message::Increment(1)),
+            Err(ActorError::TargetActorNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_shutdown_cancels_timers() {
+        let pool = ActorPool::new();
+        let id = pool.create_actor();
+
+        let timer = pool
+            .send_after(id, Duration::from_secs(60), Message::Increment(1))
+            .unwrap();
+        pool.shutdown(StopMode::Drain);
+
+        assert!(timer.is_cancelled());
+        assert!(!timer.is_pending());
+    }
+}
@@ -4,7 +4,7 @@ mod actor_update_tests {
 
     #[test]
     fn test_update_actor_state() {
-        let mut actors = ActorPool::new();
+        let actors = ActorPool::new();
         let id = actors.create_actor();
 
         assert_eq!(id, 0);
@@ -15,7 +15,7 @@ mod actor_update_tests {
         actors.update_actor_state(id).unwrap();
 
         let state = actors.get_actor_state(id).unwrap();
-        assert_eq!(state, ActorState::Inactive);
+        assert_eq!(state, ActorState::Paused);
 
         actors.update_actor_state(id).unwrap();
 